@@ -54,6 +54,8 @@ where
 pub struct CreateContributionLinkInput {
     pub group_id: ActionHash,
     pub content_target: AnyLinkableHash,
+    /// An optional structured tag used for filtering by collection and content kind
+    pub tag: Option<ContributionTag>,
 }
 
 /// Input required for registering a content update to a group
@@ -78,6 +80,109 @@ pub struct GroupAuthInput {
 pub struct GetAllGroupContentInput {
     pub group_id: ActionHash,
     pub full_trace: Option<bool>,
+    /// Only include contributions registered in this [`ContributionTag::collection`]
+    pub collection: Option<String>,
+    /// Only include contributions registered with this [`ContributionTag::kind`] (within the
+    /// selected collection or the default collection)
+    pub kind: Option<String>,
+    /// Sort results by their position in the selected collection (unpositioned content last)
+    pub sorted: Option<bool>,
+}
+
+/// Input for moving content to a new position in a group collection
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MoveGroupContentInput {
+    pub group_id: ActionHash,
+    pub collection: Option<String>,
+    pub content_id: AnyLinkableHash,
+    /// Place the content directly after this content ID (`None` for the start of the list)
+    pub after: Option<AnyLinkableHash>,
+}
+
+/// Input for getting the content positions of a group collection
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetGroupContentPositionsInput {
+    pub group_id: ActionHash,
+    pub collection: Option<String>,
+}
+
+/// Input for marking a content ID as deleted (or undeleted) in a group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupContentStateInput {
+    pub group_id: ActionHash,
+    pub content_id: AnyLinkableHash,
+}
+
+/// Input for recording that a new agent key succeeds the caller's key in a group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordKeySuccessionInput {
+    pub group_id: ActionHash,
+    pub successor: AgentPubKey,
+    /// The succession that made the caller's key an authority (for repeated key rotations)
+    pub previous: Option<ActionHash>,
+}
+
+/// Input for looking up an agent within a group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupAgentInput {
+    pub group_id: ActionHash,
+    pub agent: AgentPubKey,
+}
+
+/// Input for getting the changes in a group after a cursor
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupChangesSinceInput {
+    pub group_id: ActionHash,
+    /// `None` to get every change (eg. for an empty cache)
+    pub cursor: Option<GroupChangeCursor>,
+}
+
+/// Input for listing a group in the public group directory
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ListPublicGroupInput {
+    pub group_id: ActionHash,
+    pub name: String,
+    /// Search tags (stored lowercase)
+    pub tags: Vec<String>,
+}
+
+/// Input for encrypting content with a group's latest key
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptGroupContentInput {
+    pub group_id: ActionHash,
+    pub data: Vec<u8>,
+}
+
+/// Input for granting another contributor access to some of the caller's functions for a group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GrantGroupCapabilityInput {
+    pub group_id: ActionHash,
+    pub grantee: AgentPubKey,
+    /// A subset of [`GROUP_CAPABILITY_FUNCTIONS`]
+    pub functions: Vec<String>,
+}
+
+/// Input for revoking the group capabilities granted to a contributor
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RevokeGroupCapabilityInput {
+    pub group_id: ActionHash,
+    pub grantee: AgentPubKey,
+}
+
+/// Input for suggesting a content revision to a group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProposeContentUpdateInput {
+    pub group_id: ActionHash,
+    pub content_id: AnyLinkableHash,
+    pub content_rev: AnyLinkableHash,
+    pub proposed_rev: AnyLinkableHash,
+}
+
+/// Input for rejecting a content proposal
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RejectContentProposalInput {
+    pub proposal: ActionHash,
+    pub reason: String,
 }
 
 /// Input for following a single content's evolution in a group
@@ -90,12 +195,685 @@ pub struct GetGroupContentInput {
 
 
 
+//
+// CSR Output Structs
+//
+/// Who marked a content ID as deleted and when
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContentDeletion {
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+}
+
+/// A content ID in a group with its latest revision and deletion state
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupContentItem {
+    pub content_id: AnyLinkableHash,
+    pub latest: AnyLinkableHash,
+    pub deleted: Option<ContentDeletion>,
+    /// The contributions anchors that registered this content ID
+    pub anchors: Vec<ContentRevisionAuthority>,
+}
+
+impl GroupContentItem {
+    /// Check if this content is currently marked as deleted
+    pub fn is_deleted(&self) -> bool {
+        self.deleted.is_some()
+    }
+}
+
+/// A group that a content address is registered in, and the contributor who registered it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContentGroup {
+    pub group_id: ActionHash,
+    pub contributor: AgentPubKey,
+    pub anchor: EntryHash,
+    /// The contribution link that registered the content
+    pub contribution: ActionHash,
+}
+
+/// A group listing found in the public group directory
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PublicGroupListing {
+    pub group_id: ActionHash,
+    /// The group revision whose admins authorized the listing
+    pub group_rev: ActionHash,
+    pub name: String,
+    pub tags: Vec<String>,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+}
+
+/// An open content proposal in a group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContentProposalItem {
+    pub proposal: ActionHash,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+    pub content_id: AnyLinkableHash,
+    pub content_rev: AnyLinkableHash,
+    pub proposed_rev: AnyLinkableHash,
+}
+
+/// Activity totals for one contributions anchor of a group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContributorStats {
+    pub agent: AgentPubKey,
+    pub anchor: EntryHash,
+    pub archived: bool,
+    /// The number of contribution links
+    pub contributions: u64,
+    /// The number of contribution update links
+    pub updates: u64,
+    pub first_activity: Option<Timestamp>,
+    pub last_activity: Option<Timestamp>,
+    /// The number of current content heads that this contributor authored
+    pub heads: u64,
+}
+
+/// How a content revision was found when following a content's evolution
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ContentRevisionSource {
+    /// The content ID registered by a contribution link
+    Contribution,
+    /// A revision found using a contribution update (shortcut) link
+    Shortcut,
+    /// A revision found by tracing entry updates made by group authorities
+    FullTrace,
+}
+
+/// The contributions anchor that authorized a content revision (or registered a content ID)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContentRevisionAuthority {
+    pub anchor: EntryHash,
+    pub agent: AgentPubKey,
+    pub archived: bool,
+    /// The latest group revision for an active anchor, or the revision that archived it
+    pub group_rev: ActionHash,
+}
+
+/// One revision in a content's history
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContentRevision {
+    pub address: AnyLinkableHash,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+    /// `None` when no anchor of the latest group revision accounts for this revision
+    pub authority: Option<ContentRevisionAuthority>,
+    pub source: ContentRevisionSource,
+}
+
+
+
+//
+// Group Graph
+//
+/// The link types that appear in a [`GroupGraph`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GroupGraphLinkType {
+    GroupAuth,
+    GroupAuthArchive,
+    Contribution,
+    ContributionUpdate,
+    ContentTombstone,
+    ContentPosition,
+}
+
+/// A group revision in a [`GroupGraph`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupGraphRevision {
+    pub action: ActionHash,
+    /// The revision that this revision updated (`None` for the group ID)
+    pub previous: Option<ActionHash>,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+    pub group: GroupEntry,
+}
+
+/// A contributions anchor (active or archived) in a [`GroupGraph`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupGraphAnchor {
+    pub address: EntryHash,
+    pub anchor: ContributionAnchors,
+}
+
+/// A link in a [`GroupGraph`]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupGraphLink {
+    pub link_type: GroupGraphLinkType,
+    pub create_link_hash: ActionHash,
+    pub base: AnyLinkableHash,
+    pub target: AnyLinkableHash,
+    pub tag: LinkTag,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+}
+
+/// Every revision, anchor and link that makes up a group's state
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupGraph {
+    pub group_id: ActionHash,
+    /// Group revisions in evolution order
+    pub revisions: Vec<GroupGraphRevision>,
+    pub anchors: Vec<GroupGraphAnchor>,
+    pub links: Vec<GroupGraphLink>,
+}
+
+impl GroupGraph {
+    /// Get the latest group revision
+    pub fn latest_revision(&self) -> Option<&GroupGraphRevision> {
+        self.revisions.last()
+    }
+}
+
+
+fn short_hash<T: std::fmt::Display>(hash: &T) -> String {
+    let text = hash.to_string();
+    text[ text.len().saturating_sub(8).. ].to_string()
+}
+
+/// Render a [`GroupGraph`] as a Graphviz DOT document
+///
+/// The styles follow the diagram legend in `INTEGRITY_MODEL.md`
+/// - Red lines are valid paths from the latest group state
+/// - Grey lines are expired paths from the latest group state
+/// - Dashed lines are links (cannot be seen in validation)
+/// - Solid lines are pointers (can be seen in validation)
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// let graph : GroupGraph = call_local_zome_decode!(
+///     GroupGraph,
+///     "coop_content_csr",
+///     "export_group_graph",
+///     group_id
+/// )?;
+/// let dot = group_graph_to_dot( &graph );
+/// ```
+pub fn group_graph_to_dot(graph: &GroupGraph) -> String {
+    let latest_rev : Option<AnyLinkableHash> = graph.latest_revision()
+        .map(|revision| revision.action.to_owned().into() );
+
+    // Anchors that are reachable from the latest group revision
+    let live_anchors : Vec<AnyLinkableHash> = graph.links.iter()
+        .filter(|link| Some(&link.base) == latest_rev.as_ref() )
+        .map(|link| link.target.to_owned() )
+        .collect();
+    let is_live = |link: &GroupGraphLink| {
+        Some(&link.base) == latest_rev.as_ref() || live_anchors.contains( &link.base )
+    };
+
+    let mut lines = vec![
+        format!("digraph \"group_{}\" {{", graph.group_id ),
+        "    rankdir=LR;".to_string(),
+        "    node [fontname=\"monospace\" fontsize=10];".to_string(),
+        "    edge [fontname=\"monospace\" fontsize=8];".to_string(),
+    ];
+
+    for revision in graph.revisions.iter() {
+        lines.push(format!(
+            "    \"{}\" [shape=box label=\"Group {}\\n{} admins, {} members\"];",
+            revision.action, short_hash( &revision.action ),
+            revision.group.admins.len(), revision.group.members.len(),
+        ));
+
+        if let Some(previous) = &revision.previous {
+            lines.push(format!(
+                "    \"{}\" -> \"{}\" [style=solid color=grey label=\"evolves\"];",
+                revision.action, previous,
+            ));
+        }
+    }
+
+    for anchor in graph.anchors.iter() {
+        let label = match anchor.anchor.is_archive() {
+            true => "Archived Contributions",
+            false => "Contributions",
+        };
+        lines.push(format!(
+            "    \"{}\" [shape=hexagon label=\"{}\\nagent {}\"];",
+            anchor.address, label, short_hash( anchor.anchor.author() ),
+        ));
+        lines.push(format!(
+            "    \"{}\" -> \"{}\" [style=solid color=grey];",
+            anchor.address, anchor.anchor.group(),
+        ));
+    }
+
+    let mut content_nodes : Vec<&AnyLinkableHash> = vec![];
+    for link in graph.links.iter() {
+        match link.link_type {
+            GroupGraphLinkType::Contribution | GroupGraphLinkType::ContributionUpdate => {
+                if !content_nodes.contains( &&link.target ) {
+                    content_nodes.push( &link.target );
+                }
+            },
+            _ => (),
+        }
+    }
+
+    for content in content_nodes {
+        lines.push(format!(
+            "    \"{}\" [shape=ellipse label=\"Content {}\"];",
+            content, short_hash( content ),
+        ));
+    }
+
+    for link in graph.links.iter() {
+        lines.push(format!(
+            "    \"{}\" -> \"{}\" [style=dashed color={} label=\"{:?}\\n{} @ {}\"];",
+            link.base, link.target,
+            if is_live( link ) { "red" } else { "grey" },
+            link.link_type, short_hash( &link.author ), link.timestamp,
+        ));
+    }
+
+    lines.push("}".to_string());
+
+    lines.join("\n")
+}
+
+
+
+//
+// Group Integrity
+//
+/// A problem found while checking a group revision
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum GroupIntegrityIssue {
+    /// A contributor of the revision has no `GroupAuth` link to their contributions anchor
+    MissingGroupAuth {
+        agent: AgentPubKey,
+        anchor: EntryHash,
+    },
+    /// A contributor removed in this revision has no archived contributions anchor entry
+    MissingArchiveAnchor {
+        agent: AgentPubKey,
+        anchor: EntryHash,
+    },
+    /// A contributor removed in this revision has no `GroupAuthArchive` link to their archive
+    MissingGroupAuthArchive {
+        agent: AgentPubKey,
+        anchor: EntryHash,
+    },
+    /// An archive link from the previous revision was not carried forward to this revision
+    MissingCarriedArchive {
+        anchor: EntryHash,
+    },
+    /// A removed contributor's active anchor link was not copied to their archive anchor
+    MissingArchivedLink {
+        agent: AgentPubKey,
+        link_type: GroupGraphLinkType,
+        create_link_hash: ActionHash,
+        target: AnyLinkableHash,
+    },
+    /// A `ContributionUpdate` link has a tag that cannot be parsed
+    MalformedUpdateTag {
+        anchor: EntryHash,
+        create_link_hash: ActionHash,
+        tag: LinkTag,
+        reason: String,
+    },
+    /// A link target could not be found
+    DanglingTarget {
+        link_type: GroupGraphLinkType,
+        create_link_hash: ActionHash,
+        target: AnyLinkableHash,
+    },
+}
+
+/// The result of checking a single group revision
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupRevisionReport {
+    pub group_rev: ActionHash,
+    pub issues: Vec<GroupIntegrityIssue>,
+}
+
+impl GroupRevisionReport {
+    /// Check if no issues were found for this revision
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// The entries and links created while repairing a group revision
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupRevisionRepair {
+    pub group_rev: ActionHash,
+    pub created_anchors: Vec<ActionHash>,
+    pub created_links: Vec<ActionHash>,
+}
+
+impl GroupRevisionRepair {
+    /// Check if the repair had nothing to do
+    pub fn is_noop(&self) -> bool {
+        self.created_anchors.is_empty() && self.created_links.is_empty()
+    }
+}
+
+
+
+//
+// Group Forks
+//
+/// A line of group revisions from the group ID to a revision that has no updates
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupBranch {
+    /// The last revision of this branch
+    pub head: ActionHash,
+    /// The timestamp of the head revision
+    pub timestamp: Timestamp,
+    /// Revisions in evolution order (starting with the group ID)
+    pub revisions: Vec<ActionHash>,
+}
+
+impl GroupBranch {
+    /// Get the number of revisions in this branch
+    pub fn depth(&self) -> usize {
+        self.revisions.len()
+    }
+
+    /// Get the last revision that this branch has in common with another branch
+    pub fn fork_point(&self, other: &GroupBranch) -> Option<ActionHash> {
+        self.revisions.iter()
+            .zip( other.revisions.iter() )
+            .take_while(|(ours, theirs)| ours == theirs )
+            .last()
+            .map(|(ours, _)| ours.to_owned() )
+    }
+}
+
+/// The branches of a group's revision history
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupForks {
+    pub group_id: ActionHash,
+    /// The head of the canonical branch (see [`canonical_group_branch`])
+    pub canonical: ActionHash,
+    pub branches: Vec<GroupBranch>,
+}
+
+impl GroupForks {
+    /// Check if the group has more than one branch
+    pub fn is_forked(&self) -> bool {
+        self.branches.len() > 1
+    }
+}
+
+/// Select the canonical branch from a list of group branches
+///
+/// The rule is deterministic so that every agent agrees on the same group state
+/// 1. The branch with the most revisions
+/// 2. The branch whose head has the earliest timestamp
+/// 3. The branch whose head has the lowest action hash
+pub fn canonical_group_branch(branches: &[GroupBranch]) -> Option<&GroupBranch> {
+    branches.iter().min_by(|a, b| {
+        b.depth().cmp( &a.depth() )
+            .then( a.timestamp.cmp( &b.timestamp ) )
+            .then( a.head.get_raw_39().cmp( b.head.get_raw_39() ) )
+    })
+}
+
+/// Take the side of a three-way merge that changed a value since the fork point
+///
+/// Fails when both sides changed the value in different ways.
+fn merge_value<T: Clone + PartialEq>(
+    name: &str,
+    fork_point: &T,
+    canonical: &T,
+    other: &T,
+) -> Result<T, String> {
+    if other == fork_point || other == canonical {
+        Ok( canonical.to_owned() )
+    } else if canonical == fork_point {
+        Ok( other.to_owned() )
+    } else {
+        Err( format!("Group branches made conflicting changes to the {}", name ) )
+    }
+}
+
+/// Combine the agents that another branch added or removed since the fork point
+fn merge_agents(
+    fork_point: &[AgentPubKey],
+    canonical: &[AgentPubKey],
+    other: &[AgentPubKey],
+) -> Vec<AgentPubKey> {
+    let mut merged = canonical.to_owned();
+
+    merged.retain(|pubkey| {
+        !( fork_point.contains( pubkey ) && !other.contains( pubkey ) )
+    });
+
+    for pubkey in other.iter() {
+        if !fork_point.contains( pubkey ) && !merged.contains( pubkey ) {
+            merged.push( pubkey.to_owned() );
+        }
+    }
+
+    merged
+}
+
+/// Combine the membership windows that another branch changed since the fork point
+///
+/// Each member's window is merged separately.
+fn merge_membership_windows(
+    fork_point: &GroupEntry,
+    canonical: &GroupEntry,
+    other: &GroupEntry,
+) -> Result<Vec<MembershipWindow>, String> {
+    let mut agents : Vec<&AgentPubKey> = vec![];
+
+    for window in canonical.membership_windows.iter()
+        .chain( other.membership_windows.iter() )
+        .chain( fork_point.membership_windows.iter() )
+    {
+        if !agents.contains( &&window.agent ) {
+            agents.push( &window.agent );
+        }
+    }
+
+    let mut windows = vec![];
+
+    for agent in agents {
+        let window = merge_value(
+            "membership window",
+            &fork_point.membership_window( agent ),
+            &canonical.membership_window( agent ),
+            &other.membership_window( agent ),
+        )?;
+
+        if let Some(window) = window {
+            windows.push( window.to_owned() );
+        }
+    }
+
+    Ok( windows )
+}
+
+/// Combine the changes that another branch made since the fork point
+///
+/// The result is a copy of the canonical group with the other branch's added admins and members
+/// included and its removed ones excluded.  Any other field that was changed on the other branch
+/// is taken from it, unless the canonical branch changed the same field in a different way.
+pub fn merge_group_entries(
+    fork_point: &GroupEntry,
+    canonical: &GroupEntry,
+    other: &GroupEntry,
+) -> Result<GroupEntry, String> {
+    let mut merged = canonical.to_owned();
+
+    merged.admins = merge_agents( &fork_point.admins, &canonical.admins, &other.admins );
+    merged.members = merge_agents( &fork_point.members, &canonical.members, &other.members );
+    merged.deleted = merge_value( "deleted state", &fork_point.deleted, &canonical.deleted, &other.deleted )?;
+    merged.metadata = merge_value( "metadata", &fork_point.metadata, &canonical.metadata, &other.metadata )?;
+    merged.membership_windows = merge_membership_windows( fork_point, canonical, other )?;
+    merged.content_type_restrictions = merge_value(
+        "content type restrictions",
+        &fork_point.content_type_restrictions,
+        &canonical.content_type_restrictions,
+        &other.content_type_restrictions,
+    )?;
+
+    Ok( merged )
+}
+
+
+
+//
+// Group Changes
+//
+/// A position in a group's changes
+///
+/// Changes are ordered by timestamp and then by action hash, so the hash breaks ties between
+/// changes made at the same time.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupChangeCursor {
+    pub timestamp: Timestamp,
+    /// The create link hash (or revision action hash) of the change
+    pub hash: ActionHash,
+}
+
+impl GroupChangeCursor {
+    /// Check if a change at the given position is after this cursor
+    pub fn is_before(&self, timestamp: &Timestamp, hash: &ActionHash) -> bool {
+        ( timestamp, hash.get_raw_39() ) > ( &self.timestamp, self.hash.get_raw_39() )
+    }
+}
+
+/// The kinds of [`GroupMembershipChange`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GroupMembershipChangeKind {
+    Added,
+    Removed,
+    Resigned,
+}
+
+/// A contributor being added to, removed from, or resigning from a group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupMembershipChange {
+    pub agent: AgentPubKey,
+    pub kind: GroupMembershipChangeKind,
+    /// The group revision that made the change (or that was resigned from)
+    pub revision: ActionHash,
+    pub timestamp: Timestamp,
+}
+
+/// The changes in a group after a cursor
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupChanges {
+    pub contributions: Vec<GroupGraphLink>,
+    pub updates: Vec<GroupGraphLink>,
+    pub tombstones: Vec<GroupGraphLink>,
+    pub positions: Vec<GroupGraphLink>,
+    pub revisions: Vec<GroupGraphRevision>,
+    pub membership: Vec<GroupMembershipChange>,
+    /// The cursor to use for the next request (the input cursor when there are no changes)
+    pub cursor: Option<GroupChangeCursor>,
+}
+
+
+
+//
+// Group Encryption
+//
+/// The group key that applies to a group revision
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupKeyEpoch {
+    pub group_rev: ActionHash,
+    /// `None` if the group was not encrypted at this revision
+    pub group_key: Option<ActionHash>,
+    pub epoch: Option<u32>,
+    /// The contributors without a share of the group key (they have not registered an encryption key)
+    #[serde(default)]
+    pub pending: Vec<AgentPubKey>,
+}
+
+/// Content encrypted with a group key
+///
+/// Store this (serialized) in place of the plaintext content payload.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupCiphertext {
+    pub group_id: ActionHash,
+    /// The [`GroupKeyEntry`] that the content was encrypted with
+    pub group_key: ActionHash,
+    pub epoch: u32,
+    pub nonce: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+
+
+//
+// Group Capabilities
+//
+/// The prefix of [`GroupCapabilityTag`] strings
+pub const GROUP_CAPABILITY_TAG_PREFIX: &str = "coop_content:group:";
+
+/// The `coop_content_csr` functions that can be delegated with a group capability
+///
+/// Each of these acts on the callee's own contributions anchor for the group in its input.
+pub const GROUP_CAPABILITY_FUNCTIONS: [&str; 5] = [
+    "create_content_link",
+    "create_content_update_link",
+    "move_group_content",
+    "delete_group_content",
+    "undelete_group_content",
+];
+
+/// The tag of a group capability grant (and its claim)
+///
+/// Format: `coop_content:group:{group ID}:{function},{function},...`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupCapabilityTag {
+    pub group_id: ActionHash,
+    pub functions: Vec<String>,
+}
+
+impl GroupCapabilityTag {
+    /// Encode this as a cap grant/claim tag
+    pub fn to_tag(&self) -> String {
+        format!("{}{}:{}", GROUP_CAPABILITY_TAG_PREFIX, self.group_id, self.functions.join(",") )
+    }
+
+    /// Decode a cap grant/claim tag
+    pub fn from_tag(tag: &str) -> Result<Self, String> {
+        let (group_id, functions) = tag.strip_prefix( GROUP_CAPABILITY_TAG_PREFIX )
+            .and_then(|rest| rest.split_once(":") )
+            .ok_or(format!("Not a group capability tag: {}", tag ))?;
+
+        Ok( GroupCapabilityTag {
+            group_id: ActionHash::try_from( group_id )
+                .map_err(|err| format!("Invalid group ID in capability tag: {:?}", err ))?,
+            functions: functions.split(",")
+                .filter(|name| !name.is_empty() )
+                .map(|name| name.to_string() )
+                .collect(),
+        })
+    }
+}
+
+/// A capability to call another contributor's functions for a group
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GroupCapabilityClaim {
+    pub group_id: ActionHash,
+    pub grantor: AgentPubKey,
+    pub functions: Vec<String>,
+    pub secret: CapSecret,
+}
+
+
+
 //
 // A trait for determining a group state
 //
 /// A trait for determining an entry's group reference
 pub trait GroupRef {
     fn group_ref(&self) -> (ActionHash, ActionHash);
+
+    /// The [`KeySuccessionEntry`] that authorizes an author whose key is not in the group
+    fn key_succession(&self) -> Option<ActionHash> {
+        None
+    }
 }
 
 impl GroupRef for (ActionHash, ActionHash) {
@@ -214,6 +992,9 @@ where
 
 
 /// Checks that the author of an action is an authority in the entry's group reference
+///
+/// The author must also be allowed to write the action's entry type.  An author authorized by key
+/// succession has the entry types of the contributor they succeed.
 pub fn validate_group_member<T>(
     entry: &T,
     action: impl Into<EntryCreationAction>
@@ -232,14 +1013,78 @@ where
         None => return Err(format!("Action ({}) does not contain an entry hash", group_ref.1 )),
     };
 
-    if !group.is_contributor( author ) {
-        return Err(format!("Agent ({}) is not authorized to update content managed by group {}", author, group_ref.0 ))?;
+    let timestamp = creation_action.timestamp();
+
+    let contributor = match group.is_contributor( author ) {
+        true => {
+            if !group.is_contributor_at( author, timestamp ) {
+                return Err(format!("Agent ({}) is outside of their membership window in group {}", author, group_ref.0 ))?;
+            }
+
+            author.to_owned()
+        },
+        false => match entry.key_succession() {
+            Some(succession_addr) => validate_key_succession( &group, &group_ref.0, author, succession_addr, timestamp )?,
+            None => return Err(format!("Agent ({}) is not authorized to update content managed by group {}", author, group_ref.0 ))?,
+        },
+    };
+
+    if let EntryType::App(entry_def) = creation_action.entry_type() {
+        if !group.can_write_entry_type( &contributor, entry_def ) {
+            return Err(format!("Agent ({}) is not allowed to write entry type ({}, {}) in group {}", author, entry_def.zome_index.0, entry_def.entry_index.0, group_ref.0 ))?;
+        }
     }
 
     Ok(())
 }
 
 
+/// Checks that a key succession chain leads from a group contributor to the given agent
+///
+/// The contributor's membership window (if any) also applies to their successors.  Returns the
+/// contributor at the root of the chain.
+pub fn validate_key_succession(
+    group: &GroupEntry,
+    group_id: &ActionHash,
+    agent: &AgentPubKey,
+    succession_addr: ActionHash,
+    timestamp: &Timestamp,
+) -> Result<AgentPubKey, String> {
+    let mut expected_successor = agent.to_owned();
+    let mut next_addr = Some( succession_addr );
+
+    for _ in 0..KEY_SUCCESSION_MAX_DEPTH {
+        let addr = match next_addr {
+            Some(addr) => addr,
+            None => break,
+        };
+        let signed_action = must_get_action( addr.to_owned() )?;
+        let succession : KeySuccessionEntry = match signed_action.action().entry_hash() {
+            Some(entry_addr) => must_get_entry( entry_addr.to_owned() )?
+                .content.try_into()?,
+            None => return Err(format!("Action ({}) does not contain an entry hash", addr )),
+        };
+
+        if &succession.group_id != group_id || succession.successor != expected_successor {
+            return Err(format!("Key succession ({}) does not lead to agent ({}) in group {}", addr, agent, group_id ));
+        }
+
+        if group.is_contributor( &succession.predecessor ) {
+            if !group.is_contributor_at( &succession.predecessor, timestamp ) {
+                return Err(format!("Predecessor ({}) of agent ({}) is outside of their membership window in group {}", succession.predecessor, agent, group_id ));
+            }
+
+            return Ok( succession.predecessor );
+        }
+
+        expected_successor = succession.predecessor;
+        next_addr = succession.previous;
+    }
+
+    Err(format!("Agent ({}) is not the successor of a contributor in group {}", agent, group_id ))
+}
+
+
 //
 // Zome call helpers
 //
@@ -259,6 +1104,7 @@ where
 ///         coop_content_sdk::CreateContributionLinkInput {
 ///             group_id: ActionHash::try_from(group_id).unwrap(),
 ///             content_target: ActionHash::try_from(content_addr).unwrap().into(),
+///             tag: None,
 ///         }
 ///     )?;
 ///
@@ -305,6 +1151,7 @@ macro_rules! call_local_zome {
 ///         coop_content_sdk::CreateContributionLinkInput {
 ///             group_id: ActionHash::try_from(group_id).unwrap(),
 ///             content_target: ActionHash::try_from(content_addr).unwrap().into(),
+///             tag: None,
 ///         }
 ///     )?;
 ///
@@ -420,6 +1267,7 @@ macro_rules! register_content_to_group {
                 $crate::CreateContributionLinkInput {
                     group_id: input.entry.group_ref().0,
                     content_target: input.target.clone().into(),
+                    tag: None,
                 }
             )
         }
@@ -749,6 +1597,8 @@ macro_rules! get_all_group_content_latest {
 ///     admins: vec![ agent_info()?.agent_initial_pubkey ],
 ///     members: vec![],
 ///     deleted: None,
+///     membership_windows: vec![],
+///     content_type_restrictions: vec![],
 ///     published_at: 1688078994936,
 ///     last_updated: 1688078994936,
 ///     metadata: BTreeMap::new(),
@@ -885,6 +1735,8 @@ macro_rules! get_group {
 ///     admins: vec![ agent_info()?.agent_initial_pubkey ],
 ///     members: vec![ member_id ],
 ///     deleted: None,
+///     membership_windows: vec![],
+///     content_type_restrictions: vec![],
 ///     published_at: 1688078994936,
 ///     last_updated: 1688090053659,
 ///     metadata: BTreeMap::new(),
@@ -943,3 +1795,153 @@ macro_rules! update_group {
         $crate::update_group!( "coop_content_csr", $($def)* )
     };
 }
+
+
+/// Encrypt content with a group's latest key
+///
+/// Rule patterns
+/// - #1 - `<zome name>, <function name>, <template>`
+/// - #2 - `<zome name>, <template>`
+/// - #3 - `<template>`
+///
+/// The input template is [`EncryptGroupContentInput`].
+///
+/// This macro makes a local zome call using these default values:
+/// - Zome name: `coop_content_csr`
+/// - Function name: `encrypt_group_content`
+///
+/// Returns [`GroupCiphertext`]
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// let ciphertext = encrypt_group_content!({
+///     group_id: group_id,
+///     data: message.into_bytes(),
+/// })?;
+/// ```
+#[macro_export]
+macro_rules! encrypt_group_content {
+    ( $zome:literal, $fn_name:literal, $($def:tt)* ) => {
+        {
+            use $crate::hdk;
+
+            type Response = hdk::prelude::ExternResult<$crate::GroupCiphertext>;
+            let input = $crate::EncryptGroupContentInput $($def)*;
+            let result : Response = $crate::call_local_zome_decode!(
+                $zome,
+                $fn_name,
+                input
+            );
+            result
+        }
+    };
+    ( $zome:literal, $($def:tt)* ) => {
+        $crate::encrypt_group_content!( $zome, "encrypt_group_content", $($def)* )
+    };
+    ( $($def:tt)* ) => {
+        $crate::encrypt_group_content!( "coop_content_csr", $($def)* )
+    };
+}
+
+
+/// Decrypt content using the group key epoch that it was encrypted with
+///
+/// Rule patterns
+/// - #1 - `<zome name>, <function name>, <template>`
+/// - #2 - `<zome name>, <template>`
+/// - #3 - `<template>`
+///
+/// The input must be a [`GroupCiphertext`].
+///
+/// This macro makes a local zome call using these default values:
+/// - Zome name: `coop_content_csr`
+/// - Function name: `decrypt_group_content`
+///
+/// Returns [`Vec<u8>`]
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// let data = decrypt_group_content!( post.ciphertext )?;
+/// ```
+#[macro_export]
+macro_rules! decrypt_group_content {
+    ( $zome:literal, $fn_name:literal, $($def:tt)* ) => {
+        {
+            use $crate::hdk;
+
+            type Response = hdk::prelude::ExternResult<Vec<u8>>;
+            let input : $crate::GroupCiphertext = $($def)*;
+            let result : Response = $crate::call_local_zome_decode!(
+                $zome,
+                $fn_name,
+                input
+            );
+            result
+        }
+    };
+    ( $zome:literal, $($def:tt)* ) => {
+        $crate::decrypt_group_content!( $zome, "decrypt_group_content", $($def)* )
+    };
+    ( $($def:tt)* ) => {
+        $crate::decrypt_group_content!( "coop_content_csr", $($def)* )
+    };
+}
+
+
+/// Call a group contributor's function using a group capability claim
+///
+/// Rule patterns
+/// - #1 - `<zome name>, <claim>, <function name>, <input>`
+/// - #2 - `<claim>, <function name>, <input>`
+///
+/// The claim must be a [`GroupCapabilityClaim`] that includes the function.
+///
+/// This macro makes a remote zome call to the claim's grantor using these default values:
+/// - Zome name: `coop_content_csr`
+///
+/// Returns [`ExternIO`]
+///
+/// ##### Example: Basic Usage
+/// ```ignore
+/// let link_addr : ActionHash = call_group_remote!(
+///     claim,
+///     "create_content_link",
+///     CreateContributionLinkInput {
+///         group_id: claim.group_id.to_owned(),
+///         content_target: post_id.into(),
+///         tag: None,
+///     }
+/// )?.decode()?;
+/// ```
+#[macro_export]
+macro_rules! call_group_remote {
+    ( $zome:literal, $claim:expr, $fn_name:literal, $($input:tt)+ ) => {
+        {
+            use $crate::hdk;
+            use $crate::hdi_extensions::guest_error;
+
+            let claim : &$crate::GroupCapabilityClaim = &$claim;
+
+            if !claim.functions.iter().any(|name| name == $fn_name ) {
+                Err(guest_error!(format!("Group capability claim for group ({}) does not include function '{}'", claim.group_id, $fn_name )))
+            }
+            else {
+                match hdk::prelude::call_remote(
+                    claim.grantor.to_owned(),
+                    $zome,
+                    $fn_name.into(),
+                    Some( claim.secret.to_owned() ),
+                    $($input)+,
+                )? {
+                    ZomeCallResponse::Ok(extern_io) => Ok(extern_io),
+                    ZomeCallResponse::NetworkError(msg) => Err(guest_error!(format!("{}", msg))),
+                    ZomeCallResponse::CountersigningSession(msg) => Err(guest_error!(format!("{}", msg))),
+                    _ => Err(guest_error!(format!("Zome call response: Unauthorized"))),
+                }
+            }
+        }
+    };
+    ( $claim:expr, $fn_name:literal, $($input:tt)+ ) => {
+        $crate::call_group_remote!( "coop_content_csr", $claim, $fn_name, $($input)+ )
+    };
+}
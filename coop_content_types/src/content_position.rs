@@ -0,0 +1,246 @@
+use crate::hdi;
+use crate::contribution_tag::{
+    push_string,
+    read_string,
+};
+
+use hdi::prelude::*;
+
+
+
+//
+// Content Position Link Tag
+//
+/// The current version of the [`ContentPositionTag`] byte format
+pub const CONTENT_POSITION_TAG_VERSION: u8 = 1;
+
+/// The max byte length of a position key
+pub const POSITION_KEY_MAX: usize = 64;
+
+/// The digits used by position keys (in ascending byte order)
+pub const POSITION_KEY_DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// A payload for `LinkTypes::ContentPosition` tags
+///
+/// Byte format (v1)
+/// ```text
+/// [ version: u8 ][ collection length: u8 ][ collection ][ key length: u8 ][ key ][ original link: 39 bytes (optional) ]
+/// ```
+///
+/// Position keys are fractional indexes; content is ordered by comparing keys as byte strings.  An
+/// archived copy must carry the address of the active anchor position link that it was copied
+/// from (so that it keeps the time of the original move).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContentPositionTag {
+    /// The named collection within the group (`None` for the default collection)
+    pub collection: Option<String>,
+    /// The fractional index key
+    pub key: String,
+    /// The position link that this archived copy was made from
+    pub original: Option<ActionHash>,
+}
+
+impl ContentPositionTag {
+    /// Get the tag prefix that matches all position tags in the given collection
+    pub fn collection_prefix(collection: &str) -> Vec<u8> {
+        let mut bytes = vec![ CONTENT_POSITION_TAG_VERSION ];
+        push_string( &mut bytes, collection );
+        bytes
+    }
+
+    /// Get the collection name (an empty string for the default collection)
+    pub fn collection(&self) -> &str {
+        self.collection.as_deref().unwrap_or("")
+    }
+
+    /// Get the tag for an archived copy of the given position link
+    pub fn copy_of(&self, original: ActionHash) -> Self {
+        ContentPositionTag {
+            collection: self.collection.to_owned(),
+            key: self.key.to_owned(),
+            original: Some( original ),
+        }
+    }
+
+    /// Encode this tag using the current byte format
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        check_position_key( &self.key )?;
+
+        let mut bytes = Self::collection_prefix( self.collection() );
+        push_string( &mut bytes, &self.key );
+
+        if let Some(original) = &self.original {
+            bytes.extend_from_slice( original.get_raw_39() );
+        }
+
+        Ok( bytes )
+    }
+
+    /// Decode a tag from any supported byte format
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let version = *bytes.first()
+            .ok_or("Position tag is empty".to_string())?;
+
+        if version != CONTENT_POSITION_TAG_VERSION {
+            Err(format!("Unsupported position tag version: {}", version ))?
+        }
+
+        let mut cursor = 1;
+        let collection = read_string( bytes, &mut cursor, "collection" )?;
+        let key = read_string( bytes, &mut cursor, "key" )?;
+        let original = match &bytes[cursor..] {
+            [] => None,
+            rest if rest.len() == 39 => Some(
+                ActionHash::from_raw_39( rest.to_vec() )
+                    .map_err(|err| format!("Position tag has an invalid original link: {}", err ))?
+            ),
+            rest => Err(format!("Position tag has {} unexpected trailing bytes", rest.len() ))?,
+        };
+
+        check_position_key( &key )?;
+
+        Ok( ContentPositionTag {
+            collection: match collection.is_empty() {
+                true => None,
+                false => Some( collection ),
+            },
+            key,
+            original,
+        })
+    }
+}
+
+
+/// Check that a position key is a usable fractional index
+pub fn check_position_key(key: &str) -> Result<(), String> {
+    if key.is_empty() {
+        Err("Position key cannot be empty".to_string())?
+    }
+
+    if key.len() > POSITION_KEY_MAX {
+        Err(format!("Position key cannot be longer than {} bytes", POSITION_KEY_MAX ))?
+    }
+
+    if let Some(digit) = key.bytes().find(|digit| !POSITION_KEY_DIGITS.contains( digit ) ) {
+        Err(format!("Position key contains an invalid digit: {:?}", digit as char ))?
+    }
+
+    if key.ends_with('0') {
+        Err("Position key cannot end with '0'".to_string())?
+    }
+
+    Ok(())
+}
+
+fn digit_index(digit: u8) -> usize {
+    // Keys are checked before use so the digit is always present
+    POSITION_KEY_DIGITS.iter().position(|d| *d == digit ).unwrap_or(0)
+}
+
+fn midpoint(lower: &[u8], upper: Option<&[u8]>) -> Vec<u8> {
+    if let Some(upper) = upper {
+        // Skip the shared prefix (treating missing lower digits as '0')
+        let mut n = 0;
+        while n < upper.len() && *lower.get( n ).unwrap_or( &b'0' ) == upper[n] {
+            n += 1;
+        }
+
+        if n > 0 {
+            let mut key = upper[..n].to_vec();
+            key.extend( midpoint( lower.get( n.. ).unwrap_or( &[] ), Some( &upper[n..] ) ) );
+            return key;
+        }
+    }
+
+    let digit_lower = lower.first().map( |digit| digit_index( *digit ) ).unwrap_or( 0 );
+    let digit_upper = upper.map( |upper| digit_index( upper[0] ) ).unwrap_or( POSITION_KEY_DIGITS.len() );
+
+    if digit_upper - digit_lower > 1 {
+        vec![ POSITION_KEY_DIGITS[ (digit_lower + digit_upper).div_ceil( 2 ) ] ]
+    } else if let Some(upper) = upper.filter(|upper| upper.len() > 1 ) {
+        upper[..1].to_vec()
+    } else {
+        let mut key = vec![ POSITION_KEY_DIGITS[ digit_lower ] ];
+        key.extend( midpoint( lower.get( 1.. ).unwrap_or( &[] ), None ) );
+        key
+    }
+}
+
+/// Generate evenly spaced position keys for a list of the given length
+///
+/// Used to re-key a list when the keys between two neighbours have grown to the max length.  The
+/// keys are as short as the list length allows.
+///
+/// ##### Example
+/// ```
+/// # use coop_content_types::{ spaced_position_keys, check_position_key };
+/// let keys = spaced_position_keys( 100 );
+///
+/// assert_eq!( keys.len(), 100 );
+/// assert!( keys.windows(2).all(|pair| pair[0] < pair[1] ) );
+/// assert!( keys.iter().all(|key| key.len() <= 2 && check_position_key( key ).is_ok() ) );
+/// ```
+pub fn spaced_position_keys(count: usize) -> Vec<String> {
+    let base = POSITION_KEY_DIGITS.len() as u128;
+    let slots = count as u128 + 1;
+    let mut length = 1;
+    let mut space = base;
+
+    while space < slots {
+        length += 1;
+        space *= base;
+    }
+
+    (1..slots)
+        .map(|slot| {
+            let mut value = slot * space / slots;
+            let mut key = vec![ POSITION_KEY_DIGITS[0]; length ];
+
+            for index in (0..length).rev() {
+                key[index] = POSITION_KEY_DIGITS[ (value % base) as usize ];
+                value /= base;
+            }
+
+            // Trailing zeros are not allowed (dropping them keeps the order)
+            while key.last() == Some( &POSITION_KEY_DIGITS[0] ) {
+                key.pop();
+            }
+
+            String::from_utf8( key ).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Generate a position key that sorts between two keys
+///
+/// `None` for `lower` means the start of the list and `None` for `upper` means the end.
+///
+/// ##### Example
+/// ```
+/// # use coop_content_types::position_key_between;
+/// let first = position_key_between( None, None ).unwrap();
+/// let second = position_key_between( Some(&first), None ).unwrap();
+/// let middle = position_key_between( Some(&first), Some(&second) ).unwrap();
+///
+/// assert!( first < middle && middle < second );
+/// ```
+pub fn position_key_between(lower: Option<&str>, upper: Option<&str>) -> Result<String, String> {
+    if let Some(lower) = lower {
+        check_position_key( lower )?;
+    }
+
+    if let Some(upper) = upper {
+        check_position_key( upper )?;
+    }
+
+    if let (Some(lower), Some(upper)) = (lower, upper) {
+        if lower >= upper {
+            Err(format!("Position key '{}' must sort before '{}'", lower, upper ))?
+        }
+    }
+
+    let key = midpoint( lower.unwrap_or("").as_bytes(), upper.map( |upper| upper.as_bytes() ) );
+
+    String::from_utf8( key )
+        .map_err(|err| format!("Generated position key is not UTF8: {}", err ))
+}
@@ -0,0 +1,116 @@
+use crate::hdi;
+use crate::contribution_tag::{
+    push_string,
+    read_string,
+};
+
+use hdi::prelude::*;
+
+
+
+//
+// Content Proposal Entry
+//
+/// An entry struct suggesting a content revision to a group
+///
+/// Any agent can make a proposal.  A group contributor accepts it by registering `proposed_rev` as
+/// an update of `content_rev` under their own contributions anchor.
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct ContentProposalEntry {
+    pub group_id: ActionHash,
+    pub content_id: AnyLinkableHash,
+    /// The content revision that the proposal was made against
+    pub content_rev: AnyLinkableHash,
+    /// The suggested revision (an update of `content_rev`)
+    pub proposed_rev: AnyLinkableHash,
+}
+
+
+
+//
+// Content Proposal Resolution Link Tag
+//
+/// The current version of the [`ContentProposalResolutionTag`] byte format
+pub const CONTENT_PROPOSAL_RESOLUTION_TAG_VERSION: u8 = 1;
+
+/// The max byte length of [`ContentProposalResolutionTag::reason`]
+pub const CONTENT_PROPOSAL_REASON_MAX: usize = 255;
+
+/// A payload for `LinkTypes::ContentProposalResolution` tags
+///
+/// Byte format (v1)
+/// ```text
+/// [ version: u8 ][ accepted: u8 (0 or 1) ][ group revision: 39 bytes ][ reason length: u8 ][ reason ]
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContentProposalResolutionTag {
+    pub accepted: bool,
+    /// The group revision that made the resolver a contributor
+    pub group_rev: ActionHash,
+    /// Required when a proposal is rejected
+    pub reason: String,
+}
+
+impl ContentProposalResolutionTag {
+    /// Check the field constraints
+    pub fn check(&self) -> Result<(), String> {
+        if self.reason.len() > CONTENT_PROPOSAL_REASON_MAX {
+            Err(format!("Proposal resolution reason cannot be longer than {} bytes", CONTENT_PROPOSAL_REASON_MAX ))?
+        }
+
+        if !self.accepted && self.reason.trim().is_empty() {
+            Err("A rejected proposal must have a reason".to_string())?
+        }
+
+        Ok(())
+    }
+
+    /// Encode this tag using the current byte format
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        self.check()?;
+
+        let mut bytes = vec![ CONTENT_PROPOSAL_RESOLUTION_TAG_VERSION, self.accepted as u8 ];
+        bytes.extend_from_slice( self.group_rev.get_raw_39() );
+        push_string( &mut bytes, &self.reason );
+
+        Ok( bytes )
+    }
+
+    /// Decode a tag from any supported byte format
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let version = *bytes.first()
+            .ok_or("Proposal resolution tag is empty".to_string())?;
+
+        if version != CONTENT_PROPOSAL_RESOLUTION_TAG_VERSION {
+            Err(format!("Unsupported proposal resolution tag version: {}", version ))?
+        }
+
+        let accepted = match bytes.get( 1 ) {
+            Some(0) => false,
+            Some(1) => true,
+            Some(state) => Err(format!("Proposal resolution tag has an invalid state: {}", state ))?,
+            None => Err("Proposal resolution tag is missing the state".to_string())?,
+        };
+        let rev_bytes = bytes.get( 2..41 )
+            .ok_or("Proposal resolution tag is missing the group revision".to_string())?;
+        let group_rev = ActionHash::from_raw_39( rev_bytes.to_vec() )
+            .map_err(|err| format!("Proposal resolution tag has an invalid group revision: {}", err ))?;
+        let mut cursor = 41;
+
+        let reason = read_string( bytes, &mut cursor, "reason" )?;
+
+        if cursor != bytes.len() {
+            Err(format!("Proposal resolution tag has {} unexpected trailing bytes", bytes.len() - cursor ))?
+        }
+
+        let tag = ContentProposalResolutionTag {
+            accepted,
+            group_rev,
+            reason,
+        };
+        tag.check()?;
+
+        Ok( tag )
+    }
+}
@@ -0,0 +1,87 @@
+use crate::hdi;
+
+use hdi::prelude::*;
+
+
+
+//
+// Content Tombstone Link Tag
+//
+/// The current version of the [`ContentTombstoneTag`] byte format
+pub const CONTENT_TOMBSTONE_TAG_VERSION: u8 = 1;
+
+/// A payload for `LinkTypes::ContentTombstone` tags
+///
+/// Byte format (v1)
+/// ```text
+/// [ version: u8 ][ deleted: u8 (0 or 1) ][ timestamp: i64 BE (microseconds) ][ original link: 39 bytes (optional) ]
+/// ```
+///
+/// The timestamp is the author's claimed time of the deletion (or undeletion) and cannot be after
+/// the link timestamp.  Resolvers order tombstones by link action time instead, so an archived copy
+/// must carry the address of the active anchor tombstone link that it was copied from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContentTombstoneTag {
+    /// `true` for a deletion and `false` for an undeletion
+    pub deleted: bool,
+    pub timestamp: Timestamp,
+    /// The tombstone link that this archived copy was made from
+    pub original: Option<ActionHash>,
+}
+
+impl ContentTombstoneTag {
+    /// Get the tag for an archived copy of the given tombstone link
+    pub fn copy_of(&self, original: ActionHash) -> Self {
+        ContentTombstoneTag {
+            deleted: self.deleted,
+            timestamp: self.timestamp,
+            original: Some( original ),
+        }
+    }
+
+    /// Encode this tag using the current byte format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![ CONTENT_TOMBSTONE_TAG_VERSION, self.deleted as u8 ];
+        bytes.extend_from_slice( &self.timestamp.as_micros().to_be_bytes() );
+
+        if let Some(original) = &self.original {
+            bytes.extend_from_slice( original.get_raw_39() );
+        }
+
+        bytes
+    }
+
+    /// Decode a tag from any supported byte format
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let version = *bytes.first()
+            .ok_or("Tombstone tag is empty".to_string())?;
+
+        if version != CONTENT_TOMBSTONE_TAG_VERSION {
+            Err(format!("Unsupported tombstone tag version: {}", version ))?
+        }
+
+        if bytes.len() != 10 && bytes.len() != 49 {
+            Err(format!("Tombstone tag must be 10 or 49 bytes; not {}", bytes.len() ))?
+        }
+
+        let deleted = match bytes[1] {
+            0 => false,
+            1 => true,
+            state => Err(format!("Tombstone tag has an invalid state: {}", state ))?,
+        };
+        let micros = i64::from_be_bytes( bytes[2..10].try_into().unwrap() );
+        let original = match bytes.get( 10..49 ) {
+            Some(addr_bytes) => Some(
+                ActionHash::from_raw_39( addr_bytes.to_vec() )
+                    .map_err(|err| format!("Tombstone tag has an invalid original link: {}", err ))?
+            ),
+            None => None,
+        };
+
+        Ok( ContentTombstoneTag {
+            deleted,
+            timestamp: Timestamp::from_micros( micros ),
+            original,
+        })
+    }
+}
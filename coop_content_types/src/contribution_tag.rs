@@ -0,0 +1,176 @@
+use crate::hdi;
+use crate::hdi_extensions;
+
+use hdi::prelude::*;
+use hdi_extensions::guest_error;
+
+
+
+//
+// Contribution Link Tag
+//
+/// The current version of the [`ContributionTag`] byte format
+pub const CONTRIBUTION_TAG_VERSION: u8 = 1;
+
+/// The max byte length of [`ContributionTag::collection`]
+pub const CONTRIBUTION_TAG_COLLECTION_MAX: usize = 64;
+
+/// The max byte length of [`ContributionTag::kind`]
+pub const CONTRIBUTION_TAG_KIND_MAX: usize = 64;
+
+/// The max byte length of [`ContributionTag::label`]
+pub const CONTRIBUTION_TAG_LABEL_MAX: usize = 64;
+
+/// A structured payload for `LinkTypes::Contribution` tags
+///
+/// Byte format (v1)
+/// ```text
+/// [ version: u8 ][ collection length: u8 ][ collection ][ kind length: u8 ][ kind ][ label length: u8 ][ label ][ created at: u64 BE (optional) ]
+/// ```
+///
+/// The collection and kind are placed first so that links can be filtered using a `tag_prefix`
+/// (see [`ContributionTag::collection_prefix`] and [`ContributionTag::kind_prefix`]).  Untagged
+/// contributions belong to the default collection.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContributionTag {
+    /// The named collection within the group (`None` for the default collection)
+    pub collection: Option<String>,
+    /// The content kind (eg. the entry type name)
+    pub kind: String,
+    /// A short human readable label
+    pub label: Option<String>,
+    /// A hint for when the content was created
+    pub created_at: Option<u64>,
+}
+
+pub(crate) fn push_string(bytes: &mut Vec<u8>, text: &str) {
+    bytes.push( text.len() as u8 );
+    bytes.extend_from_slice( text.as_bytes() );
+}
+
+pub(crate) fn read_string(bytes: &[u8], cursor: &mut usize, name: &str) -> Result<String, String> {
+    let length = *bytes.get( *cursor )
+        .ok_or(format!("Tag is missing the {} length", name ))? as usize;
+    *cursor += 1;
+
+    let text_bytes = bytes.get( *cursor..*cursor + length )
+        .ok_or(format!("Tag {} is shorter than its declared length ({})", name, length ))?;
+    *cursor += length;
+
+    String::from_utf8( text_bytes.to_vec() )
+        .map_err(|err| format!("Tag {} must be a UTF8 string: {}", name, err ))
+}
+
+impl ContributionTag {
+    /// Get the tag prefix that matches all contribution tags in the given collection
+    pub fn collection_prefix(collection: &str) -> Vec<u8> {
+        let mut bytes = vec![ CONTRIBUTION_TAG_VERSION ];
+        push_string( &mut bytes, collection );
+        bytes
+    }
+
+    /// Get the tag prefix that matches all contribution tags of the given kind in a collection
+    pub fn kind_prefix(collection: &str, kind: &str) -> Vec<u8> {
+        let mut bytes = Self::collection_prefix( collection );
+        push_string( &mut bytes, kind );
+        bytes
+    }
+
+    /// Get the collection name (an empty string for the default collection)
+    pub fn collection(&self) -> &str {
+        self.collection.as_deref().unwrap_or("")
+    }
+
+    /// Check the field constraints
+    pub fn check(&self) -> Result<(), String> {
+        if self.collection().len() > CONTRIBUTION_TAG_COLLECTION_MAX {
+            Err(format!("Contribution tag collection cannot be longer than {} bytes", CONTRIBUTION_TAG_COLLECTION_MAX ))?
+        }
+
+        if self.kind.is_empty() && self.collection().is_empty() {
+            Err("Contribution tag must have a kind or a collection".to_string())?
+        }
+
+        if self.kind.len() > CONTRIBUTION_TAG_KIND_MAX {
+            Err(format!("Contribution tag kind cannot be longer than {} bytes", CONTRIBUTION_TAG_KIND_MAX ))?
+        }
+
+        if let Some(label) = &self.label {
+            if label.len() > CONTRIBUTION_TAG_LABEL_MAX {
+                Err(format!("Contribution tag label cannot be longer than {} bytes", CONTRIBUTION_TAG_LABEL_MAX ))?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encode this tag using the current byte format
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        self.check()?;
+
+        let mut bytes = Self::kind_prefix( self.collection(), &self.kind );
+
+        push_string( &mut bytes, self.label.as_deref().unwrap_or("") );
+
+        if let Some(created_at) = self.created_at {
+            bytes.extend_from_slice( &created_at.to_be_bytes() );
+        }
+
+        Ok( bytes )
+    }
+
+    /// Decode a tag from the current byte format
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let version = *bytes.first()
+            .ok_or("Contribution tag is empty".to_string())?;
+        let mut cursor = 1;
+
+        if version != CONTRIBUTION_TAG_VERSION {
+            Err(format!("Unsupported contribution tag version: {}", version ))?
+        }
+
+        let collection = read_string( bytes, &mut cursor, "collection" )?;
+        let kind = read_string( bytes, &mut cursor, "kind" )?;
+        let label = read_string( bytes, &mut cursor, "label" )?;
+
+        let created_at = match &bytes[cursor..] {
+            [] => None,
+            rest => Some( u64::from_be_bytes(
+                rest.try_into()
+                    .map_err(|_| format!("Contribution tag created at hint must be 8 bytes; not {}", rest.len() ))?
+            )),
+        };
+
+        let tag = ContributionTag {
+            collection: match collection.is_empty() {
+                true => None,
+                false => Some( collection ),
+            },
+            kind,
+            label: match label.is_empty() {
+                true => None,
+                false => Some( label ),
+            },
+            created_at,
+        };
+        tag.check()?;
+
+        Ok( tag )
+    }
+
+    /// Decode the collection name of any contribution link tag (empty tags are the default collection)
+    pub fn collection_of(bytes: &[u8]) -> Result<String, String> {
+        match bytes.is_empty() {
+            true => Ok( String::new() ),
+            false => Ok( Self::from_bytes( bytes )?.collection().to_string() ),
+        }
+    }
+}
+
+impl TryFrom<ContributionTag> for LinkTag {
+    type Error = WasmError;
+
+    fn try_from(tag: ContributionTag) -> Result<Self, Self::Error> {
+        Ok( LinkTag::new( tag.to_bytes().map_err(|message| guest_error!(message) )? ) )
+    }
+}
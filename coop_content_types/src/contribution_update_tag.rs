@@ -0,0 +1,95 @@
+use crate::hdi;
+use crate::hdi_extensions;
+
+use hdi::prelude::*;
+use hdi_extensions::AnyLinkableHashTransformer;
+
+
+
+//
+// Contribution Update Link Tag
+//
+/// The current version of the [`ContributionUpdateTag`] byte format
+pub const CONTRIBUTION_UPDATE_TAG_VERSION: u8 = 1;
+
+/// The byte length of a [`ContributionUpdateTag`] in the current format
+pub const CONTRIBUTION_UPDATE_TAG_LENGTH: usize = 1 + 39 + 39;
+
+/// A payload for `LinkTypes::ContributionUpdate` tags
+///
+/// Byte format (v1)
+/// ```text
+/// [ version: u8 ][ content ID: 39 bytes ][ content revision: 39 bytes ]
+/// ```
+///
+/// Legacy format (still readable)
+/// ```text
+/// "{content ID}:{content revision}"
+/// ```
+///
+/// The content ID is placed first so that links can be filtered using a `tag_prefix` (see
+/// [`ContributionUpdateTag::content_id_prefix`]).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContributionUpdateTag {
+    /// The ID (create address) of the content
+    pub content_id: AnyLinkableHash,
+    /// The revision that the link target is an update of
+    pub content_rev: AnyLinkableHash,
+}
+
+impl ContributionUpdateTag {
+    /// Get the tag prefix that matches all update tags for the given content ID
+    pub fn content_id_prefix(content_id: &AnyLinkableHash) -> Vec<u8> {
+        let mut bytes = vec![ CONTRIBUTION_UPDATE_TAG_VERSION ];
+        bytes.extend_from_slice( content_id.get_raw_39() );
+        bytes
+    }
+
+    /// Get the tag prefix that matches legacy (string) update tags for the given content ID
+    pub fn legacy_content_id_prefix(content_id: &AnyLinkableHash) -> Vec<u8> {
+        format!("{}:", content_id ).into_bytes()
+    }
+
+    /// Encode this tag using the current byte format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Self::content_id_prefix( &self.content_id );
+        bytes.extend_from_slice( self.content_rev.get_raw_39() );
+        bytes
+    }
+
+    /// Decode a tag from any supported byte format
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        match bytes.first() {
+            Some(&CONTRIBUTION_UPDATE_TAG_VERSION) => Self::from_v1_bytes( bytes ),
+            Some(_) => Self::from_legacy_bytes( bytes ),
+            None => Err("Update tag is empty".to_string()),
+        }
+    }
+
+    fn from_v1_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != CONTRIBUTION_UPDATE_TAG_LENGTH {
+            Err(format!("Update tag must be {} bytes; not {}", CONTRIBUTION_UPDATE_TAG_LENGTH, bytes.len() ))?
+        }
+
+        Ok( ContributionUpdateTag {
+            content_id: AnyLinkableHash::from_raw_39( bytes[1..40].to_vec() )
+                .map_err(|err| format!("Invalid tag part 1: {}", err ))?,
+            content_rev: AnyLinkableHash::from_raw_39( bytes[40..79].to_vec() )
+                .map_err(|err| format!("Invalid tag part 2: {}", err ))?,
+        })
+    }
+
+    fn from_legacy_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let tag_str = String::from_utf8( bytes.to_vec() )
+            .map_err(|err| format!("Legacy update tag must be a UTF8 string: {}", err ))?;
+        let (tag_id, tag_rev) = tag_str.split_once(":")
+            .ok_or(format!("Legacy update tag is missing the ':' separator: {}", tag_str ))?;
+
+        Ok( ContributionUpdateTag {
+            content_id: AnyLinkableHash::try_from_string( tag_id )
+                .map_err(|err| format!("Invalid tag part 1: {}", err ))?,
+            content_rev: AnyLinkableHash::try_from_string( tag_rev )
+                .map_err(|err| format!("Invalid tag part 2: {}", err ))?,
+        })
+    }
+}
@@ -0,0 +1,54 @@
+use crate::hdi;
+
+use hdi::prelude::*;
+
+
+
+//
+// Governing Group Revision
+//
+/// The byte length of the group revision that ends every link tag on a contributions anchor
+pub const GOVERNING_REVISION_LENGTH: usize = 39;
+
+/// Append the governing group revision to a contributions anchor link tag payload
+///
+/// Links on a contributions anchor (contributions, updates, positions and tombstones) end with the
+/// group revision that authorised them so that validation can check the author's membership window
+/// against that revision.  The payload comes first so that `tag_prefix` filters keep working.
+///
+/// ##### Example
+/// ```
+/// # use coop_content_types::*;
+/// # use coop_content_types::hdi::prelude::*;
+/// let group_rev = ActionHash::from_raw_36( vec![ 0; 36 ] );
+/// let tag = with_governing_revision( vec![ 1, 2, 3 ], &group_rev );
+///
+/// assert_eq!( split_governing_revision( &tag ), Ok( (&[ 1, 2, 3 ][..], group_rev) ) );
+/// ```
+pub fn with_governing_revision(mut payload: Vec<u8>, group_rev: &ActionHash) -> Vec<u8> {
+    payload.extend_from_slice( group_rev.get_raw_39() );
+    payload
+}
+
+/// Split a contributions anchor link tag into its payload and governing group revision
+pub fn split_governing_revision(tag: &[u8]) -> Result<(&[u8], ActionHash), String> {
+    if tag.len() < GOVERNING_REVISION_LENGTH {
+        Err(format!("Link tag must end with a {} byte governing group revision; tag is {} bytes", GOVERNING_REVISION_LENGTH, tag.len() ))?
+    }
+
+    let (payload, group_rev) = tag.split_at( tag.len() - GOVERNING_REVISION_LENGTH );
+    let group_rev = ActionHash::from_raw_39( group_rev.to_vec() )
+        .map_err(|err| format!("Link tag has an invalid governing group revision: {}", err ))?;
+
+    Ok( (payload, group_rev) )
+}
+
+/// Get the payload of a contributions anchor link tag
+///
+/// Tags without a governing group revision (made before it was required) are returned whole.
+pub fn governed_payload(tag: &[u8]) -> &[u8] {
+    match split_governing_revision( tag ) {
+        Ok((payload, _)) => payload,
+        Err(_) => tag,
+    }
+}
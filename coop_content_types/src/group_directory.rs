@@ -0,0 +1,178 @@
+use crate::hdi;
+use crate::contribution_tag::{
+    push_string,
+    read_string,
+};
+
+use hdi::prelude::*;
+use hdi::hash_path::path::{
+    Path,
+    Component,
+};
+
+
+
+//
+// Group Directory Link Tag
+//
+/// The current version of the [`GroupDirectoryTag`] byte format
+pub const GROUP_DIRECTORY_TAG_VERSION: u8 = 1;
+
+/// The root path component of the public group directory
+pub const GROUP_DIRECTORY_ROOT: &str = "group_directory";
+
+/// The number of (lowercase) name characters used to shard the directory by name
+pub const GROUP_DIRECTORY_SHARD_LENGTH: usize = 2;
+
+/// The max byte length of [`GroupDirectoryTag::name`]
+pub const GROUP_DIRECTORY_NAME_MAX: usize = 64;
+
+/// The max byte length of each of [`GroupDirectoryTag::tags`]
+pub const GROUP_DIRECTORY_TAG_MAX: usize = 32;
+
+/// The max number of [`GroupDirectoryTag::tags`]
+pub const GROUP_DIRECTORY_TAGS_MAX: usize = 8;
+
+/// Get the directory shard for a group name (or name search)
+pub fn group_directory_shard(name: &str) -> String {
+    name.to_lowercase().chars()
+        .take( GROUP_DIRECTORY_SHARD_LENGTH )
+        .collect()
+}
+
+fn directory_path(kind: &str, key: &str) -> Path {
+    Path::from( vec![
+        Component::from( GROUP_DIRECTORY_ROOT.to_string() ),
+        Component::from( kind.to_string() ),
+        Component::from( key.to_string() ),
+    ])
+}
+
+/// Get the path anchor for groups whose names start with the given shard
+pub fn group_directory_name_anchor(name: &str) -> ExternResult<EntryHash> {
+    directory_path( "name", &group_directory_shard( name ) ).path_entry_hash()
+}
+
+/// Get the path anchor for groups with the given tag
+pub fn group_directory_tag_anchor(tag: &str) -> ExternResult<EntryHash> {
+    directory_path( "tag", &tag.to_lowercase() ).path_entry_hash()
+}
+
+/// Get the path anchor for every listing of the given group
+pub fn group_directory_group_anchor(group_id: &ActionHash) -> ExternResult<EntryHash> {
+    directory_path( "group", &group_id.to_string() ).path_entry_hash()
+}
+
+/// A payload for `LinkTypes::GroupDirectory` tags
+///
+/// Byte format (v1)
+/// ```text
+/// [ version: u8 ][ group revision: 39 bytes ][ name length: u8 ][ name ][ tag count: u8 ]( [ tag length: u8 ][ tag ] )*
+/// ```
+///
+/// The same tag is used on the name shard link, on each tag link and on the group link of a listing
+/// so that any of them can be resolved without the others.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupDirectoryTag {
+    /// The group revision whose admins authorized the listing
+    pub group_rev: ActionHash,
+    pub name: String,
+    /// Lowercase search tags
+    pub tags: Vec<String>,
+}
+
+impl GroupDirectoryTag {
+    /// Check the field constraints
+    pub fn check(&self) -> Result<(), String> {
+        if self.name.chars().count() < GROUP_DIRECTORY_SHARD_LENGTH {
+            Err(format!("Group directory name must be at least {} characters", GROUP_DIRECTORY_SHARD_LENGTH ))?
+        }
+
+        if self.name.len() > GROUP_DIRECTORY_NAME_MAX {
+            Err(format!("Group directory name cannot be longer than {} bytes", GROUP_DIRECTORY_NAME_MAX ))?
+        }
+
+        if self.tags.len() > GROUP_DIRECTORY_TAGS_MAX {
+            Err(format!("Group directory listing cannot have more than {} tags", GROUP_DIRECTORY_TAGS_MAX ))?
+        }
+
+        for tag in self.tags.iter() {
+            if tag.is_empty() || tag.len() > GROUP_DIRECTORY_TAG_MAX {
+                Err(format!("Group directory tags must be 1 to {} bytes: {:?}", GROUP_DIRECTORY_TAG_MAX, tag ))?
+            }
+
+            if tag != &tag.to_lowercase() {
+                Err(format!("Group directory tags must be lowercase: {:?}", tag ))?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the search path anchors that this listing can be linked from
+    pub fn anchors(&self) -> ExternResult<Vec<EntryHash>> {
+        let mut anchors = vec![ group_directory_name_anchor( &self.name )? ];
+
+        for tag in self.tags.iter() {
+            anchors.push( group_directory_tag_anchor( tag )? );
+        }
+
+        Ok( anchors )
+    }
+
+    /// Encode this tag using the current byte format
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        self.check()?;
+
+        let mut bytes = vec![ GROUP_DIRECTORY_TAG_VERSION ];
+        bytes.extend_from_slice( self.group_rev.get_raw_39() );
+        push_string( &mut bytes, &self.name );
+        bytes.push( self.tags.len() as u8 );
+
+        for tag in self.tags.iter() {
+            push_string( &mut bytes, tag );
+        }
+
+        Ok( bytes )
+    }
+
+    /// Decode a tag from any supported byte format
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let version = *bytes.first()
+            .ok_or("Group directory tag is empty".to_string())?;
+
+        if version != GROUP_DIRECTORY_TAG_VERSION {
+            Err(format!("Unsupported group directory tag version: {}", version ))?
+        }
+
+        let rev_bytes = bytes.get( 1..40 )
+            .ok_or("Group directory tag is missing the group revision".to_string())?;
+        let group_rev = ActionHash::from_raw_39( rev_bytes.to_vec() )
+            .map_err(|err| format!("Group directory tag has an invalid group revision: {}", err ))?;
+        let mut cursor = 40;
+
+        let name = read_string( bytes, &mut cursor, "name" )?;
+        let count = *bytes.get( cursor )
+            .ok_or("Group directory tag is missing the tag count".to_string())? as usize;
+        cursor += 1;
+
+        let mut tags = vec![];
+
+        for _ in 0..count {
+            tags.push( read_string( bytes, &mut cursor, "search tag" )? );
+        }
+
+        if cursor != bytes.len() {
+            Err(format!("Group directory tag has {} unexpected trailing bytes", bytes.len() - cursor ))?
+        }
+
+        let tag = GroupDirectoryTag {
+            group_rev,
+            name,
+            tags,
+        };
+        tag.check()?;
+
+        Ok( tag )
+    }
+}
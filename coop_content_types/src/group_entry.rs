@@ -64,6 +64,12 @@ pub struct GroupEntry {
     pub members: Vec<AgentPubKey>,
     /// An indicator of whether this group is still active
     pub deleted: Option<bool>,
+    /// Optional time bounds for members (members without a window are not time-bounded)
+    #[serde(default)]
+    pub membership_windows: Vec<MembershipWindow>,
+    /// Optional allow-lists of entry types (members without a restriction are not restricted)
+    #[serde(default)]
+    pub content_type_restrictions: Vec<ContentTypeRestriction>,
 
     // common fields
     pub published_at: u64,
@@ -86,6 +92,57 @@ impl GroupEntry {
         self.contributors().contains( agent )
     }
 
+    /// Check if the given agent is an admin, or a member within their membership window
+    pub fn is_contributor_at(&self, agent: &AgentPubKey, timestamp: &Timestamp) -> bool {
+        if self.is_admin( agent ) {
+            return true;
+        }
+
+        self.is_member( agent ) && match self.membership_window( agent ) {
+            Some(window) => window.contains( timestamp ),
+            None => true,
+        }
+    }
+
+    /// Get the membership window of the given member
+    pub fn membership_window(&self, agent: &AgentPubKey) -> Option<&MembershipWindow> {
+        self.membership_windows.iter()
+            .find(|window| &window.agent == agent )
+    }
+
+    /// Get the entry types the given agent is allowed to write (`None` means unrestricted)
+    ///
+    /// Admins are never restricted.  A member matched by more than one restriction can write the
+    /// entry types of all of them.
+    pub fn allowed_entry_types(&self, agent: &AgentPubKey) -> Option<Vec<&AllowedEntryType>> {
+        if self.is_admin( agent ) {
+            return None;
+        }
+
+        let restrictions : Vec<&ContentTypeRestriction> = self.content_type_restrictions.iter()
+            .filter(|restriction| restriction.applies_to( self, agent ) )
+            .collect();
+
+        if restrictions.is_empty() {
+            return None;
+        }
+
+        Some(
+            restrictions.into_iter()
+                .flat_map(|restriction| restriction.entry_types.iter() )
+                .collect()
+        )
+    }
+
+    /// Check if the given agent is allowed to write the given app entry type
+    pub fn can_write_entry_type(&self, agent: &AgentPubKey, entry_def: &AppEntryDef) -> bool {
+        match self.allowed_entry_types( agent ) {
+            Some(entry_types) => entry_types.iter()
+                .any(|entry_type| entry_type.matches( entry_def ) ),
+            None => true,
+        }
+    }
+
     /// Check if the given agent is an admin
     pub fn is_admin(&self, agent: &AgentPubKey) -> bool {
         self.admins.contains( agent )
@@ -93,7 +150,7 @@ impl GroupEntry {
 
     /// Check if the given agent is a member (not an admin)
     pub fn is_member(&self, agent: &AgentPubKey) -> bool {
-        self.admins.contains( agent )
+        self.members.contains( agent )
     }
 
     /// Return the differences between this group and the given group
@@ -121,6 +178,73 @@ impl GroupEntry {
     }
 }
 
+/// A time window in which a group member can contribute
+///
+/// Both bounds are inclusive and compared against action timestamps.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MembershipWindow {
+    pub agent: AgentPubKey,
+    pub valid_from: Option<Timestamp>,
+    pub valid_until: Option<Timestamp>,
+}
+
+impl MembershipWindow {
+    /// Check if the given timestamp is within this window
+    pub fn contains(&self, timestamp: &Timestamp) -> bool {
+        let started = match &self.valid_from {
+            Some(valid_from) => timestamp >= valid_from,
+            None => true,
+        };
+        let ended = match &self.valid_until {
+            Some(valid_until) => timestamp > valid_until,
+            None => false,
+        };
+
+        started && !ended
+    }
+}
+
+/// The contributors that a [`ContentTypeRestriction`] applies to
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ContentTypeScope {
+    /// A single member
+    Agent(AgentPubKey),
+    /// Every member (not admins)
+    Members,
+}
+
+/// An app entry type identified by its zome and entry def indexes
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AllowedEntryType {
+    pub zome_index: ZomeIndex,
+    pub entry_index: EntryDefIndex,
+}
+
+impl AllowedEntryType {
+    /// Check if the given app entry def is this entry type
+    pub fn matches(&self, entry_def: &AppEntryDef) -> bool {
+        self.zome_index == entry_def.zome_index
+            && self.entry_index == entry_def.entry_index
+    }
+}
+
+/// An allow-list of the entry types that a member (or role) can write
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContentTypeRestriction {
+    pub scope: ContentTypeScope,
+    pub entry_types: Vec<AllowedEntryType>,
+}
+
+impl ContentTypeRestriction {
+    /// Check if this restriction applies to the given agent in the given group
+    pub fn applies_to(&self, group: &GroupEntry, agent: &AgentPubKey) -> bool {
+        group.is_member( agent ) && match &self.scope {
+            ContentTypeScope::Agent(scoped_agent) => scoped_agent == agent,
+            ContentTypeScope::Members => true,
+        }
+    }
+}
+
 /// The result of a group comparison
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ContributorsDiff {
@@ -248,3 +372,191 @@ impl<'de> serde::Deserialize<'de> for ContributionAnchorTypes {
         )
     }
 }
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(seed: u8) -> AgentPubKey {
+        AgentPubKey::from_raw_36( vec![ seed; 36 ] )
+    }
+
+    fn group_entry(admins: &[&AgentPubKey], members: &[&AgentPubKey]) -> GroupEntry {
+        GroupEntry {
+            admins: admins.iter().map(|pubkey| (*pubkey).to_owned() ).collect(),
+            members: members.iter().map(|pubkey| (*pubkey).to_owned() ).collect(),
+            deleted: None,
+            membership_windows: vec![],
+            content_type_restrictions: vec![],
+            published_at: 0,
+            last_updated: 0,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    fn entry_def(entry_index: u8) -> AppEntryDef {
+        AppEntryDef::new( entry_index.into(), 0.into(), EntryVisibility::Public )
+    }
+
+    fn allowed(entry_index: u8) -> AllowedEntryType {
+        AllowedEntryType {
+            zome_index: 0.into(),
+            entry_index: entry_index.into(),
+        }
+    }
+
+    fn window(agent: &AgentPubKey, valid_from: Option<i64>, valid_until: Option<i64>) -> MembershipWindow {
+        MembershipWindow {
+            agent: agent.to_owned(),
+            valid_from: valid_from.map( Timestamp::from_micros ),
+            valid_until: valid_until.map( Timestamp::from_micros ),
+        }
+    }
+
+    #[test]
+    fn unrestricted_member_can_write_any_entry_type() {
+        let alice = agent(1);
+        let bobby = agent(2);
+        let group = group_entry( &[ &alice ], &[ &bobby ] );
+
+        assert_eq!( group.allowed_entry_types( &bobby ), None );
+        assert!( group.can_write_entry_type( &bobby, &entry_def(1) ) );
+    }
+
+    #[test]
+    fn restricted_member_can_only_write_allowed_entry_types() {
+        let alice = agent(1);
+        let bobby = agent(2);
+        let carol = agent(3);
+        let mut group = group_entry( &[ &alice ], &[ &bobby, &carol ] );
+        group.content_type_restrictions.push( ContentTypeRestriction {
+            scope: ContentTypeScope::Agent( bobby.to_owned() ),
+            entry_types: vec![ allowed(1) ],
+        });
+
+        assert_eq!( group.allowed_entry_types( &bobby ), Some( vec![ &allowed(1) ] ) );
+        assert!( group.can_write_entry_type( &bobby, &entry_def(1) ) );
+        assert!( !group.can_write_entry_type( &bobby, &entry_def(2) ) );
+
+        // The restriction is scoped to bobby only
+        assert!( group.can_write_entry_type( &carol, &entry_def(2) ) );
+    }
+
+    #[test]
+    fn member_matched_by_several_restrictions_can_write_all_of_them() {
+        let alice = agent(1);
+        let bobby = agent(2);
+        let mut group = group_entry( &[ &alice ], &[ &bobby ] );
+        group.content_type_restrictions.push( ContentTypeRestriction {
+            scope: ContentTypeScope::Members,
+            entry_types: vec![ allowed(1) ],
+        });
+        group.content_type_restrictions.push( ContentTypeRestriction {
+            scope: ContentTypeScope::Agent( bobby.to_owned() ),
+            entry_types: vec![ allowed(2) ],
+        });
+
+        assert!( group.can_write_entry_type( &bobby, &entry_def(1) ) );
+        assert!( group.can_write_entry_type( &bobby, &entry_def(2) ) );
+        assert!( !group.can_write_entry_type( &bobby, &entry_def(3) ) );
+    }
+
+    #[test]
+    fn admin_is_never_restricted() {
+        let alice = agent(1);
+        let mut group = group_entry( &[ &alice ], &[] );
+        group.content_type_restrictions.push( ContentTypeRestriction {
+            scope: ContentTypeScope::Agent( alice.to_owned() ),
+            entry_types: vec![ allowed(1) ],
+        });
+        group.content_type_restrictions.push( ContentTypeRestriction {
+            scope: ContentTypeScope::Members,
+            entry_types: vec![ allowed(1) ],
+        });
+
+        assert_eq!( group.allowed_entry_types( &alice ), None );
+        assert!( group.can_write_entry_type( &alice, &entry_def(2) ) );
+    }
+
+    #[test]
+    fn member_without_window_is_always_a_contributor() {
+        let alice = agent(1);
+        let bobby = agent(2);
+        let group = group_entry( &[ &alice ], &[ &bobby ] );
+
+        assert!( group.is_contributor_at( &bobby, &Timestamp::from_micros( 0 ) ) );
+        assert!( group.is_contributor_at( &bobby, &Timestamp::from_micros( i64::MAX ) ) );
+    }
+
+    #[test]
+    fn membership_window_bounds_are_inclusive() {
+        let alice = agent(1);
+        let bobby = agent(2);
+        let mut group = group_entry( &[ &alice ], &[ &bobby ] );
+        group.membership_windows.push( window( &bobby, Some( 100 ), Some( 200 ) ) );
+
+        assert!( !group.is_contributor_at( &bobby, &Timestamp::from_micros( 99 ) ) );
+        assert!( group.is_contributor_at( &bobby, &Timestamp::from_micros( 100 ) ) );
+        assert!( group.is_contributor_at( &bobby, &Timestamp::from_micros( 200 ) ) );
+        assert!( !group.is_contributor_at( &bobby, &Timestamp::from_micros( 201 ) ) );
+    }
+
+    #[test]
+    fn open_ended_membership_windows() {
+        let alice = agent(1);
+        let bobby = agent(2);
+        let carol = agent(3);
+        let mut group = group_entry( &[ &alice ], &[ &bobby, &carol ] );
+        group.membership_windows.push( window( &bobby, Some( 100 ), None ) );
+        group.membership_windows.push( window( &carol, None, Some( 200 ) ) );
+
+        assert!( !group.is_contributor_at( &bobby, &Timestamp::from_micros( 99 ) ) );
+        assert!( group.is_contributor_at( &bobby, &Timestamp::from_micros( i64::MAX ) ) );
+        assert!( group.is_contributor_at( &carol, &Timestamp::from_micros( 0 ) ) );
+        assert!( !group.is_contributor_at( &carol, &Timestamp::from_micros( 201 ) ) );
+    }
+
+    #[test]
+    fn admin_is_never_time_bounded() {
+        let alice = agent(1);
+        let mut group = group_entry( &[ &alice ], &[] );
+        group.membership_windows.push( window( &alice, Some( 100 ), Some( 200 ) ) );
+
+        assert!( group.is_contributor_at( &alice, &Timestamp::from_micros( 0 ) ) );
+        assert!( group.is_contributor_at( &alice, &Timestamp::from_micros( 300 ) ) );
+    }
+
+    #[test]
+    fn non_contributor_is_never_a_contributor() {
+        let alice = agent(1);
+        let bobby = agent(2);
+        let mut group = group_entry( &[ &alice ], &[] );
+        group.membership_windows.push( window( &bobby, None, None ) );
+
+        assert!( !group.is_contributor_at( &bobby, &Timestamp::from_micros( 0 ) ) );
+    }
+
+    #[test]
+    fn is_member_matches_members_only() {
+        let alice = agent(1);
+        let bobby = agent(2);
+        let carol = agent(3);
+        let group = group_entry( &[ &alice ], &[ &bobby ] );
+
+        assert!( group.is_member( &bobby ) );
+        assert!( !group.is_member( &alice ) );
+        assert!( !group.is_member( &carol ) );
+    }
+
+    #[test]
+    fn is_admin_matches_admins_only() {
+        let alice = agent(1);
+        let bobby = agent(2);
+        let group = group_entry( &[ &alice ], &[ &bobby ] );
+
+        assert!( group.is_admin( &alice ) );
+        assert!( !group.is_admin( &bobby ) );
+    }
+}
@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+use crate::hdi;
+
+use hdi::prelude::*;
+
+
+
+//
+// Group Key Entry
+//
+/// The byte length of an X25519 public key
+pub const X25519_PUB_KEY_LENGTH: usize = 32;
+
+/// The byte length of an XSalsa20Poly1305 nonce
+pub const XSALSA20_POLY1305_NONCE_LENGTH: usize = 24;
+
+/// A group key encrypted for one contributor
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GroupKeyShare {
+    pub agent: AgentPubKey,
+    /// The contributor's X25519 public key that the share was encrypted for
+    pub recipient_key: Vec<u8>,
+    pub nonce: Vec<u8>,
+    pub encrypted_key: Vec<u8>,
+}
+
+/// An entry struct sharing one epoch of a group's symmetric key with the contributors of a group
+/// revision
+///
+/// The key is exported from the author's keystore to each contributor using `x_salsa20_poly1305`
+/// and the X25519 keys of the author and the contributor.  The epoch must increase when a
+/// contributor of the previous key is not a contributor of `group_rev`.
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct GroupKeyEntry {
+    pub group_id: ActionHash,
+    pub group_rev: ActionHash,
+    pub epoch: u32,
+    /// The author's X25519 public key that the shares were encrypted with
+    pub sender_key: Vec<u8>,
+    pub shares: Vec<GroupKeyShare>,
+    /// The group key that this one replaces or re-shares
+    pub previous: Option<ActionHash>,
+}
+
+impl GroupKeyEntry {
+    /// Get the agents that this key was shared with
+    pub fn recipients(&self) -> HashSet<AgentPubKey> {
+        self.shares.iter()
+            .map(|share| share.agent.to_owned() )
+            .collect()
+    }
+
+    /// Get the share for the given agent
+    pub fn share_for(&self, agent: &AgentPubKey) -> Option<&GroupKeyShare> {
+        self.shares.iter()
+            .find(|share| &share.agent == agent )
+    }
+}
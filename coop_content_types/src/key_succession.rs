@@ -0,0 +1,26 @@
+use crate::hdi;
+
+use hdi::prelude::*;
+
+
+
+//
+// Key Succession Entry
+//
+/// The max number of successions that are followed when resolving a succession chain
+pub const KEY_SUCCESSION_MAX_DEPTH: usize = 16;
+
+/// An entry struct recording that a new agent key succeeds an old key within a group
+///
+/// The entry is authored by the predecessor key.  The predecessor must either be a contributor of
+/// `group_rev` or the successor of the `previous` succession (for repeated key rotations).
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct KeySuccessionEntry {
+    pub group_id: ActionHash,
+    pub group_rev: ActionHash,
+    pub predecessor: AgentPubKey,
+    pub successor: AgentPubKey,
+    /// The succession that made the predecessor an authority of the group
+    pub previous: Option<ActionHash>,
+}
@@ -1,6 +1,24 @@
 mod group_entry;
+mod contribution_tag;
+mod contribution_update_tag;
+mod content_position;
+mod content_tombstone;
+mod key_succession;
+mod group_directory;
+mod group_key;
+mod content_proposal;
+mod governing_revision;
 
 pub use hdi_extensions;
 pub use hdi_extensions::hdi;
 
 pub use group_entry::*;
+pub use contribution_tag::*;
+pub use contribution_update_tag::*;
+pub use content_position::*;
+pub use content_tombstone::*;
+pub use key_succession::*;
+pub use group_directory::*;
+pub use group_key::*;
+pub use content_proposal::*;
+pub use governing_revision::*;
@@ -699,6 +699,853 @@ function general_tests () {
 	expect( evolutions		).to.have.length( 3 );
     });
 
+    it("should export group graph", async function () {
+	const graph			= await alice_client.call( DNA_NAME, COOP_ZOME, "export_group_graph", g1_addr );
+	log.debug("Group graph: %s", json.debug( graph ) );
+
+	expect( graph.revisions		).to.have.length( 3 );
+	expect( graph.revisions.map( rev => String(new ActionHash(rev.action)) ) ).to.deep.equal(
+	    [ g1_addr, g1a_addr, g1b_addr ].map( addr => String(new ActionHash(addr)) )
+	);
+
+	const link_types		= new Set( graph.links.map( link => link.link_type ) );
+	expect( link_types		).to.have.all.keys(
+	    "GroupAuth", "GroupAuthArchive", "Contribution", "ContributionUpdate",
+	);
+    });
+
+    it("should check group integrity", async function () {
+	const reports			= await alice_client.call( DNA_NAME, COOP_ZOME, "check_group_integrity", g1_addr );
+	log.debug("Group integrity reports: %s", json.debug( reports ) );
+
+	expect( reports			).to.have.length( 3 );
+
+	for ( let report of reports ) {
+	    expect( report.issues	).to.have.length( 0 );
+	}
+    });
+
+    it("should repair group revision without changes", async function () {
+	const repair			= await alice_client.call( DNA_NAME, COOP_ZOME, "repair_group_revision", g1a_addr );
+	log.debug("Group revision repair: %s", json.debug( repair ) );
+
+	expect( repair.created_anchors	).to.have.length( 0 );
+	expect( repair.created_links	).to.have.length( 0 );
+    });
+
+    it("should detect and merge forked group revisions", async function () {
+	const group_input		= createGroupInput(
+	    [ alice_client.agent_id, emily_client.agent_id ],
+	    bobby_client.agent_id, carol_client.agent_id,
+	);
+	const f1_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", group_input );
+	const f1			= intoStruct( await alice_client.call( DNA_NAME, GOOD_ZOME, "get_group", f1_addr ), GroupStruct );
+
+	// Alice removes carol while emily adds david on the same base
+	await alice_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+	    "base": f1_addr,
+	    "entry": Object.assign({}, f1, {
+		"members": [ bobby_client.agent_id ],
+	    }),
+	});
+	await delay( 1_000 );
+	await emily_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+	    "base": f1_addr,
+	    "entry": Object.assign({}, f1, {
+		"members": [ bobby_client.agent_id, carol_client.agent_id, david_client.agent_id ],
+	    }),
+	});
+	await delay( 1_000 );
+
+	const forks			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_forks", f1_addr );
+	log.debug("Group forks: %s", json.debug( forks ) );
+
+	expect( forks.branches		).to.have.length( 2 );
+
+	const merge_addr		= await alice_client.call( DNA_NAME, COOP_ZOME, "merge_group_branches", f1_addr );
+	const merged			= intoStruct( await alice_client.call( DNA_NAME, GOOD_ZOME, "get_group", f1_addr ), GroupStruct );
+	log.debug("Merged group (%s): %s", new ActionHash(merge_addr), json.debug( merged ) );
+
+	expect( merged.members.map( String ) ).to.have.members(
+	    [ bobby_client.agent_id, david_client.agent_id ].map( String )
+	);
+
+	const merged_forks		= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_forks", f1_addr );
+	log.debug("Group forks after merge: %s", json.debug( merged_forks ) );
+
+	expect( merged_forks.branches	).to.have.length( 1 );
+	expect( String(new ActionHash(merged_forks.canonical)) ).to.equal( String(new ActionHash(merge_addr)) );
+
+	const reports			= await alice_client.call( DNA_NAME, COOP_ZOME, "check_group_integrity", f1_addr );
+	for ( let report of reports ) {
+	    expect( report.issues	).to.have.length( 0 );
+	}
+    });
+
+    it("should filter group content by contribution kind", async function () {
+	const k1_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	));
+	const note_addr			= new ActionHash( crypto.randomBytes(32) );
+
+	await alice_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k1_addr,
+	    "content_target": new ActionHash( crypto.randomBytes(32) ),
+	});
+	await alice_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k1_addr,
+	    "content_target": note_addr,
+	    "tag": {
+		"kind": "note",
+		"label": "Meeting notes",
+		"created_at": Date.now(),
+	    },
+	});
+
+	const all_targets		= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content_targets", {
+	    "group_id": k1_addr,
+	});
+	expect( all_targets		).to.have.length( 2 );
+
+	const note_targets		= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content_targets", {
+	    "group_id": k1_addr,
+	    "kind": "note",
+	});
+	log.debug("Note targets: %s", json.debug( note_targets ) );
+
+	expect( note_targets		).to.have.length( 1 );
+	expect( String(new ActionHash(note_targets[0][0])) ).to.equal( String(note_addr) );
+    });
+
+    it("should list group content per collection", async function () {
+	const k2_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	));
+	const tags			= [
+	    null,
+	    { "collection": "docs",	"kind": "page" },
+	    { "collection": "docs",	"kind": "faq" },
+	    { "collection": "issues",	"kind": "bug" },
+	];
+
+	for ( let tag of tags ) {
+	    await alice_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+		"group_id": k2_addr,
+		"content_target": new ActionHash( crypto.randomBytes(32) ),
+		tag,
+	    });
+	}
+
+	async function count ( filter ) {
+	    const targets		= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content_targets", {
+		"group_id": k2_addr,
+		...filter,
+	    });
+	    return targets.length;
+	}
+
+	expect( await count({})					).to.equal( 4 );
+	expect( await count({ "collection": "" })		).to.equal( 1 );
+	expect( await count({ "collection": "docs" })		).to.equal( 2 );
+	expect( await count({ "collection": "docs", "kind": "faq" }) ).to.equal( 1 );
+	expect( await count({ "collection": "issues" })		).to.equal( 1 );
+    });
+
+    it("should reorder group content", async function () {
+	const k3_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	));
+	const [ a, b, c ]		= [ 1, 2, 3 ].map( () => new ActionHash( crypto.randomBytes(32) ) );
+
+	for ( let content_target of [ a, b, c ] ) {
+	    await alice_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+		"group_id": k3_addr,
+		content_target,
+	    });
+	}
+
+	async function move ( content_id, after ) {
+	    return await alice_client.call( DNA_NAME, COOP_ZOME, "move_group_content", {
+		"group_id": k3_addr,
+		content_id,
+		after,
+	    });
+	}
+
+	await move( a, null );
+	await move( b, a );
+	await move( c, a );
+
+	const positions			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_content_positions", {
+	    "group_id": k3_addr,
+	});
+	log.debug("Content positions: %s", json.debug( positions ) );
+
+	expect( positions.map( ([id]) => String(new ActionHash(id)) ) ).to.deep.equal(
+	    [ a, c, b ].map( String )
+	);
+
+	// Move 'a' to the end
+	await move( a, b );
+
+	const targets			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content_targets", {
+	    "group_id": k3_addr,
+	    "sorted": true,
+	});
+
+	expect( targets.map( ([id]) => String(new ActionHash(id)) ) ).to.deep.equal(
+	    [ c, b, a ].map( String )
+	);
+
+	await expect_reject( async () => {
+	    await move( new ActionHash( crypto.randomBytes(32) ), null );
+	}, "is not in this group collection" );
+    });
+
+    it("should get content (C2) history with provenance", async function () {
+	const history			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_content_history", {
+	    "group_id": g1_addr,
+	    "content_id": c2_addr,
+	});
+	log.debug("Content history: %s", json.debug( history ) );
+
+	expect( history			).to.have.length( 3 );
+	expect( history.map( rev => rev.source ) ).to.deep.equal([ "Contribution", "Shortcut", "Shortcut" ]);
+	expect( String(new ActionHash(history[1].address)) ).to.equal( String(c2a_addr) );
+	expect( String(new AgentPubKey(history[1].author)) ).to.equal( String(alice_client.agent_id) );
+
+	for ( let revision of history ) {
+	    expect( revision.authority		).to.not.be.null;
+	    expect( String(new AgentPubKey(revision.authority.agent)) ).to.equal( String(new AgentPubKey(revision.author)) );
+	}
+    });
+
+    it("should get group contributor stats", async function () {
+	const stats			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_contributor_stats", g1_addr );
+	log.debug("Contributor stats: %s", json.debug( stats ) );
+
+	const alice_stats		= stats.find( item => !item.archived
+						      && String(new AgentPubKey(item.agent)) === String(alice_client.agent_id) );
+
+	expect( alice_stats.contributions	).to.equal( 2 );
+	expect( alice_stats.updates		).to.equal( 1 );
+	expect( alice_stats.first_activity	).to.be.at.most( alice_stats.last_activity );
+
+	const total_heads		= stats.reduce( (sum, item) => sum + item.heads, 0 );
+	expect( total_heads			).to.be.at.least( 5 );
+    });
+
+    it("should mark group content as deleted and undeleted", async function () {
+	const k4_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	const content_target		= new ActionHash( crypto.randomBytes(32) );
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k4_addr,
+	    content_target,
+	});
+
+	async function get_item () {
+	    const items			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content", {
+		"group_id": k4_addr,
+	    });
+	    expect( items			).to.have.length( 1 );
+	    return items[0];
+	}
+
+	expect( (await get_item()).deleted	).to.be.null;
+
+	await alice_client.call( DNA_NAME, COOP_ZOME, "delete_group_content", {
+	    "group_id": k4_addr,
+	    "content_id": content_target,
+	});
+
+	const item			= await get_item();
+	log.debug("Deleted item: %s", json.debug( item ) );
+
+	expect( String(new AgentPubKey(item.deleted.author)) ).to.equal( String(alice_client.agent_id) );
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "undelete_group_content", {
+	    "group_id": k4_addr,
+	    "content_id": content_target,
+	});
+
+	expect( (await get_item()).deleted	).to.be.null;
+    });
+
+    it("should let a member leave a group", async function () {
+	const k5_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	const [ before, after ]		= [ 1, 2 ].map( () => new ActionHash( crypto.randomBytes(32) ) );
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k5_addr,
+	    "content_target": before,
+	});
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "leave_group", k5_addr );
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k5_addr,
+	    "content_target": after,
+	});
+
+	const resignations		= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_resignations", k5_addr );
+	expect( resignations		).to.have.length( 1 );
+
+	const targets			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content_targets", {
+	    "group_id": k5_addr,
+	});
+	expect( targets.map( ([id]) => String(new ActionHash(id)) ) ).to.deep.equal( [ String(before) ] );
+
+	// The next admin update must formalise the resignation
+	const group			= intoStruct( await alice_client.call( DNA_NAME, GOOD_ZOME, "get_group", k5_addr ), GroupStruct );
+
+	await expect_reject( async () => {
+	    await alice_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+		"base": k5_addr,
+		"entry": group,
+	    });
+	}, "has resigned from the group" );
+
+	group.members			= [];
+	await alice_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+	    "base": k5_addr,
+	    "entry": group,
+	});
+	const updated			= await alice_client.call( DNA_NAME, GOOD_ZOME, "get_group", k5_addr );
+
+	expect( updated.members		).to.have.length( 0 );
+    });
+
+    it("should merge contributions from a succeeding agent key", async function () {
+	const k6_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	const [ old_key, new_key ]	= [ 1, 2 ].map( () => new ActionHash( crypto.randomBytes(32) ) );
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k6_addr,
+	    "content_target": old_key,
+	});
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "record_key_succession", {
+	    "group_id": k6_addr,
+	    "successor": felix_client.agent_id,
+	});
+	await felix_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k6_addr,
+	    "content_target": new_key,
+	});
+
+	const successors		= await alice_client.call( DNA_NAME, COOP_ZOME, "get_key_successors", {
+	    "group_id": k6_addr,
+	    "agent": bobby_client.agent_id,
+	});
+	expect( successors.map( key => String(new AgentPubKey(key)) ) ).to.deep.equal( [ String(felix_client.agent_id) ] );
+
+	const targets			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content_targets", {
+	    "group_id": k6_addr,
+	});
+	expect( targets.map( ([id]) => String(new ActionHash(id)) ) ).to.have.members(
+	    [ old_key, new_key ].map( String )
+	);
+    });
+
+    it("should keep a succeeding agent key's contributions after the member is removed", async function () {
+	const k6b_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	const [ old_key, new_key ]	= [ 1, 2 ].map( () => new ActionHash( crypto.randomBytes(32) ) );
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k6b_addr,
+	    "content_target": old_key,
+	});
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "record_key_succession", {
+	    "group_id": k6b_addr,
+	    "successor": felix_client.agent_id,
+	});
+	await felix_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k6b_addr,
+	    "content_target": new_key,
+	});
+
+	const group			= intoStruct( await alice_client.call( DNA_NAME, GOOD_ZOME, "get_group", k6b_addr ), GroupStruct );
+	group.members			= [];
+	await alice_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+	    "base": k6b_addr,
+	    "entry": group,
+	});
+
+	const targets			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content_targets", {
+	    "group_id": k6b_addr,
+	});
+	expect( targets.map( ([id]) => String(new ActionHash(id)) ) ).to.have.members(
+	    [ old_key, new_key ].map( String )
+	);
+
+	const reports			= await alice_client.call( DNA_NAME, COOP_ZOME, "check_group_integrity", k6b_addr );
+	for ( let report of reports ) {
+	    expect( report.issues	).to.have.length( 0 );
+	}
+    });
+
+    it("should ignore member contributions outside of their membership window", async function () {
+	const k7_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	const [ inside, outside ]	= [ 1, 2 ].map( () => new ActionHash( crypto.randomBytes(32) ) );
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k7_addr,
+	    "content_target": inside,
+	});
+
+	// Close bobby's membership window
+	const group			= intoStruct( await alice_client.call( DNA_NAME, GOOD_ZOME, "get_group", k7_addr ), GroupStruct );
+	group.membership_windows	= [{
+	    "agent": bobby_client.agent_id,
+	    "valid_from": null,
+	    "valid_until": Date.now() * 1000,
+	}];
+	await alice_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+	    "base": k7_addr,
+	    "entry": group,
+	});
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k7_addr,
+	    "content_target": outside,
+	});
+
+	const targets			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content_targets", {
+	    "group_id": k7_addr,
+	});
+	expect( targets.map( ([id]) => String(new ActionHash(id)) ) ).to.deep.equal( [ String(inside) ] );
+    });
+
+    it("should list re-added member content once with both anchors", async function () {
+	const k9_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	const content_target		= new ActionHash( crypto.randomBytes(32) );
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k9_addr,
+	    "content_target": content_target,
+	});
+
+	// Remove and then re-add bobby
+	const group			= intoStruct( await alice_client.call( DNA_NAME, GOOD_ZOME, "get_group", k9_addr ), GroupStruct );
+	const members			= group.members;
+	group.members			= [];
+	const k9a_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+	    "base": k9_addr,
+	    "entry": group,
+	});
+	group.members			= members;
+	await alice_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+	    "base": k9a_addr,
+	    "entry": group,
+	});
+
+	const items			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_all_group_content", {
+	    "group_id": k9_addr,
+	});
+	log.debug("Re-added member content: %s", json.debug( items ) );
+
+	expect( items			).to.have.length( 1 );
+	expect( items[0].anchors.map( anchor => anchor.archived ) ).to.have.members( [ true, false ] );
+	for ( let anchor of items[0].anchors )
+	    expect( String(new AgentPubKey(anchor.agent)) ).to.equal( String(bobby_client.agent_id) );
+    });
+
+    it("should get group changes since a cursor", async function () {
+	const k10_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	const [ first, second ]		= [ 1, 2 ].map( () => new ActionHash( crypto.randomBytes(32) ) );
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k10_addr,
+	    "content_target": first,
+	});
+
+	const initial			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_changes_since", {
+	    "group_id": k10_addr,
+	    "cursor": null,
+	});
+	log.debug("Initial changes: %s", json.debug( initial ) );
+
+	expect( initial.revisions		).to.have.length( 1 );
+	expect( initial.contributions		).to.have.length( 1 );
+	expect( initial.membership.map( change => change.kind ) ).to.deep.equal( [ "Added", "Added" ] );
+	expect( initial.cursor			).to.not.be.null;
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": k10_addr,
+	    "content_target": second,
+	});
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "leave_group", k10_addr );
+
+	const delta			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_changes_since", {
+	    "group_id": k10_addr,
+	    "cursor": initial.cursor,
+	});
+	log.debug("Delta changes: %s", json.debug( delta ) );
+
+	expect( delta.revisions			).to.have.length( 0 );
+	expect( delta.contributions.map( link => String(new ActionHash(link.target)) ) ).to.deep.equal( [ String(second) ] );
+	expect( delta.membership.map( change => change.kind ) ).to.deep.equal( [ "Resigned" ] );
+    });
+
+    it("should find the groups that content is registered in", async function () {
+	const content_target		= new ActionHash( crypto.randomBytes(32) );
+	const group_ids			= [];
+
+	for ( let i = 0; i < 2; i++ ) {
+	    group_ids.push( await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+		[ alice_client.agent_id ],
+		bobby_client.agent_id,
+	    )) );
+	}
+
+	for ( let group_id of group_ids ) {
+	    await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+		"group_id": group_id,
+		"content_target": content_target,
+	    });
+	}
+
+	const groups			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_content_groups", content_target );
+	log.debug("Content groups: %s", json.debug( groups ) );
+
+	expect( groups.map( group => String(new ActionHash(group.group_id)) ) ).to.have.members(
+	    group_ids.map( addr => String(new ActionHash(addr)) )
+	);
+	for ( let group of groups )
+	    expect( String(new AgentPubKey(group.contributor)) ).to.equal( String(bobby_client.agent_id) );
+
+	// Deleting the contribution also removes it from the index
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "delete_group_auth_anchor_content_links", [
+	    {
+		"group_id": group_ids[0],
+		"author": bobby_client.agent_id,
+	    },
+	    content_target,
+	]);
+
+	const remaining			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_content_groups", content_target );
+	log.debug("Remaining content groups: %s", json.debug( remaining ) );
+
+	expect( remaining.map( group => String(new ActionHash(group.group_id)) ) ).to.deep.equal( [
+	    String(new ActionHash(group_ids[1])),
+	]);
+    });
+
+    it("should list, search and delist a public group", async function () {
+	const group_id			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	const name			= `Directory ${crypto.randomBytes(4).toString("hex")}`;
+	const tag			= `tag-${crypto.randomBytes(4).toString("hex")}`;
+
+	await alice_client.call( DNA_NAME, COOP_ZOME, "list_public_group", {
+	    "group_id": group_id,
+	    "name": name,
+	    "tags": [ tag.toUpperCase() ],
+	});
+
+	const found			= await bobby_client.call( DNA_NAME, COOP_ZOME, "search_public_groups", name.slice( 0, 12 ).toLowerCase() );
+	log.debug("Search results: %s", json.debug( found ) );
+
+	expect( found.map( listing => String(new ActionHash(listing.group_id)) ) ).to.include( String(group_id) );
+
+	const tagged			= await bobby_client.call( DNA_NAME, COOP_ZOME, "get_public_groups_by_tag", tag );
+
+	expect( tagged				).to.have.length( 1 );
+	expect( tagged[0].name			).to.equal( name );
+	expect( tagged[0].tags			).to.deep.equal( [ tag ] );
+
+	await expect_reject( async () => {
+	    await bobby_client.call( DNA_NAME, COOP_ZOME, "list_public_group", {
+		"group_id": group_id,
+		"name": name,
+		"tags": [],
+	    });
+	}, "Only admins of the group can list it" );
+
+	await expect_reject( async () => {
+	    await alice_client.call( DNA_NAME, COOP_ZOME, "list_public_group", {
+		"group_id": group_id,
+		"name": "D",
+		"tags": [],
+	    });
+	}, "name must be at least 2 characters" );
+
+	// Relisting under a new name removes the old name and tag links
+	const renamed			= `Renamed ${crypto.randomBytes(4).toString("hex")}`;
+
+	await alice_client.call( DNA_NAME, COOP_ZOME, "list_public_group", {
+	    "group_id": group_id,
+	    "name": renamed,
+	    "tags": [],
+	});
+
+	{
+	    const found			= await bobby_client.call( DNA_NAME, COOP_ZOME, "search_public_groups", name.slice( 0, 12 ).toLowerCase() );
+	    expect( found.map( listing => String(new ActionHash(listing.group_id)) ) ).to.not.include( String(group_id) );
+
+	    const tagged		= await bobby_client.call( DNA_NAME, COOP_ZOME, "get_public_groups_by_tag", tag );
+	    expect( tagged			).to.have.length( 0 );
+
+	    const renamed_found		= await bobby_client.call( DNA_NAME, COOP_ZOME, "search_public_groups", renamed.toLowerCase() );
+	    expect( renamed_found.map( listing => String(new ActionHash(listing.group_id)) ) ).to.include( String(group_id) );
+	}
+
+	await alice_client.call( DNA_NAME, COOP_ZOME, "delist_public_group", group_id );
+
+	const remaining			= await bobby_client.call( DNA_NAME, COOP_ZOME, "search_public_groups", renamed.toLowerCase() );
+
+	expect( remaining.map( listing => String(new ActionHash(listing.group_id)) ) ).to.not.include( String(group_id) );
+    });
+
+    it("should encrypt group content and rotate the group key when a member is removed", async function () {
+	await alice_client.call( DNA_NAME, COOP_ZOME, "register_encryption_key", null );
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "register_encryption_key", null );
+
+	const group_id			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	await alice_client.call( DNA_NAME, COOP_ZOME, "create_group_key", group_id );
+
+	const message			= Buffer.from("Secret message");
+	const ciphertext		= await bobby_client.call( DNA_NAME, COOP_ZOME, "encrypt_group_content", {
+	    "group_id": group_id,
+	    "data": message,
+	});
+	log.debug("Group ciphertext: %s", json.debug( ciphertext ) );
+
+	expect( ciphertext.epoch		).to.equal( 0 );
+	expect( Buffer.from( ciphertext.data ) ).to.not.deep.equal( message );
+
+	const plaintext			= await alice_client.call( DNA_NAME, COOP_ZOME, "decrypt_group_content", ciphertext );
+
+	expect( Buffer.from( plaintext )	).to.deep.equal( message );
+
+	// Remove bobby
+	const group			= intoStruct( await alice_client.call( DNA_NAME, GOOD_ZOME, "get_group", group_id ), GroupStruct );
+	group.members			= [];
+	await alice_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+	    "base": group_id,
+	    "entry": group,
+	});
+
+	const epochs			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_key_epochs", group_id );
+	log.debug("Group key epochs: %s", json.debug( epochs ) );
+
+	expect( epochs.map( revision => revision.epoch ) ).to.deep.equal( [ 0, 1 ] );
+
+	await expect_reject( async () => {
+	    await bobby_client.call( DNA_NAME, COOP_ZOME, "encrypt_group_content", {
+		"group_id": group_id,
+		"data": message,
+	    });
+	}, "does not have a share of group key epoch 1" );
+    });
+
+    it("should leave members without an encryption key pending until the key is re-shared", async function () {
+	await alice_client.call( DNA_NAME, COOP_ZOME, "register_encryption_key", null );
+
+	const group_id			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    carol_client.agent_id,
+	));
+	await alice_client.call( DNA_NAME, COOP_ZOME, "create_group_key", group_id );
+
+	{
+	    const epochs		= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_key_epochs", group_id );
+	    log.debug("Group key epochs: %s", json.debug( epochs ) );
+
+	    expect( epochs			).to.have.length( 1 );
+	    expect( epochs[0].epoch		).to.equal( 0 );
+	    expect( epochs[0].pending.map( key => String(new AgentPubKey(key)) ) ).to.deep.equal( [ String(carol_client.agent_id) ] );
+	}
+
+	await expect_reject( async () => {
+	    await carol_client.call( DNA_NAME, COOP_ZOME, "encrypt_group_content", {
+		"group_id": group_id,
+		"data": Buffer.from("Secret message"),
+	    });
+	}, "does not have a share of group key epoch 0" );
+
+	await carol_client.call( DNA_NAME, COOP_ZOME, "register_encryption_key", null );
+	await alice_client.call( DNA_NAME, COOP_ZOME, "share_group_key_with_pending", group_id );
+
+	{
+	    const epochs		= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_key_epochs", group_id );
+	    log.debug("Group key epochs: %s", json.debug( epochs ) );
+
+	    expect( epochs[0].epoch		).to.equal( 0 );
+	    expect( epochs[0].pending		).to.have.length( 0 );
+	}
+
+	const ciphertext		= await carol_client.call( DNA_NAME, COOP_ZOME, "encrypt_group_content", {
+	    "group_id": group_id,
+	    "data": Buffer.from("Secret message"),
+	});
+
+	expect( ciphertext.epoch		).to.equal( 0 );
+    });
+
+    it("should skip re-sharing the group key when the updating admin does not have a share", async function () {
+	const group_id			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id, david_client.agent_id ],
+	    emily_client.agent_id,
+	));
+	await alice_client.call( DNA_NAME, COOP_ZOME, "create_group_key", group_id );
+	await emily_client.call( DNA_NAME, COOP_ZOME, "register_encryption_key", null );
+
+	// David is an admin without a share while Emily is pending with a registered key
+	const group			= intoStruct( await david_client.call( DNA_NAME, GOOD_ZOME, "get_group", group_id ), GroupStruct );
+	await david_client.call( DNA_NAME, GOOD_ZOME, "update_group", {
+	    "base": group_id,
+	    "entry": group,
+	});
+
+	{
+	    const epochs		= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_key_epochs", group_id );
+	    log.debug("Group key epochs: %s", json.debug( epochs ) );
+
+	    expect( epochs.map( revision => revision.epoch ) ).to.deep.equal( [ 0, 0 ] );
+	    expect( epochs[1].pending.map( key => String(new AgentPubKey(key)) ) ).to.include( String(emily_client.agent_id) );
+	}
+
+	await alice_client.call( DNA_NAME, COOP_ZOME, "share_group_key_with_pending", group_id );
+
+	const ciphertext		= await emily_client.call( DNA_NAME, COOP_ZOME, "encrypt_group_content", {
+	    "group_id": group_id,
+	    "data": Buffer.from("Secret message"),
+	});
+
+	expect( ciphertext.epoch		).to.equal( 0 );
+    });
+
+    it("should grant, deliver and revoke a group capability", async function () {
+	const group_id			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+
+	await expect_reject( async () => {
+	    await alice_client.call( DNA_NAME, COOP_ZOME, "grant_group_capability", {
+		"group_id": group_id,
+		"grantee": bobby_client.agent_id,
+		"functions": [ "update_group" ],
+	    });
+	}, "cannot be granted with a group capability" );
+
+	const claim			= await alice_client.call( DNA_NAME, COOP_ZOME, "grant_group_capability", {
+	    "group_id": group_id,
+	    "grantee": bobby_client.agent_id,
+	    "functions": [ "create_content_link" ],
+	});
+	log.debug("Group capability claim: %s", json.debug( claim ) );
+
+	const claims			= await bobby_client.call( DNA_NAME, COOP_ZOME, "get_group_capability_claims", group_id );
+
+	expect( claims				).to.have.length( 1 );
+	expect( String(new AgentPubKey(claims[0].grantor)) ).to.equal( String(alice_client.agent_id) );
+	expect( claims[0].functions		).to.deep.equal( [ "create_content_link" ] );
+
+	await expect_reject( async () => {
+	    await bobby_client.call( DNA_NAME, COOP_ZOME, "receive_group_capability", {
+		...claim,
+		"grantor": carol_client.agent_id,
+	    });
+	}, "is not a contributor of group" );
+
+	const revoked			= await alice_client.call( DNA_NAME, COOP_ZOME, "revoke_group_capability", {
+	    "group_id": group_id,
+	    "grantee": bobby_client.agent_id,
+	});
+
+	expect( revoked				).to.have.length( 1 );
+    });
+
+    it("should accept and reject content proposals from non-members", async function () {
+	const group_id			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	));
+	// Entry hashes are used so that validation does not trace the (fake) revisions
+	const content_id		= new EntryHash( crypto.randomBytes(32) );
+	const suggestions		= [ 1, 2 ].map( () => new EntryHash( crypto.randomBytes(32) ) );
+
+	await bobby_client.call( DNA_NAME, COOP_ZOME, "create_content_link", {
+	    "group_id": group_id,
+	    "content_target": content_id,
+	});
+
+	const proposal_ids		= [];
+	for ( let proposed_rev of suggestions ) {
+	    proposal_ids.push( await carol_client.call( DNA_NAME, COOP_ZOME, "propose_content_update", {
+		"group_id": group_id,
+		"content_id": content_id,
+		"content_rev": content_id,
+		"proposed_rev": proposed_rev,
+	    }) );
+	}
+
+	const open			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_open_content_proposals", group_id );
+	log.debug("Open proposals: %s", json.debug( open ) );
+
+	expect( open				).to.have.length( 2 );
+
+	await alice_client.call( DNA_NAME, COOP_ZOME, "accept_content_proposal", proposal_ids[0] );
+	await alice_client.call( DNA_NAME, COOP_ZOME, "reject_content_proposal", {
+	    "proposal": proposal_ids[1],
+	    "reason": "Not a typo",
+	});
+
+	const remaining			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_open_content_proposals", group_id );
+
+	expect( remaining			).to.have.length( 0 );
+
+	const changes			= await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_changes_since", {
+	    "group_id": group_id,
+	    "cursor": null,
+	});
+
+	expect( changes.updates.map( link => String(new EntryHash(link.target)) ) ).to.deep.equal( [ String(suggestions[0]) ] );
+	expect( String(new AgentPubKey(changes.updates[0].author)) ).to.equal( String(alice_client.agent_id) );
+
+	await expect_reject( async () => {
+	    await carol_client.call( DNA_NAME, COOP_ZOME, "accept_content_proposal", proposal_ids[1] );
+	}, "Only contributors of the group can resolve" );
+
+	const unregistered_id		= new EntryHash( crypto.randomBytes(32) );
+	const unregistered_proposal	= await carol_client.call( DNA_NAME, COOP_ZOME, "propose_content_update", {
+	    "group_id": group_id,
+	    "content_id": unregistered_id,
+	    "content_rev": unregistered_id,
+	    "proposed_rev": new EntryHash( crypto.randomBytes(32) ),
+	});
+
+	await expect_reject( async () => {
+	    await alice_client.call( DNA_NAME, COOP_ZOME, "accept_content_proposal", unregistered_proposal );
+	}, "is not in group content" );
+    });
+
     //
     // Failure checks
     //
@@ -708,6 +1555,58 @@ function general_tests () {
 	}, "Record not found" );
     });
 
+    it("should fail to get content latest because content is not in the group", async function () {
+	await expect_reject( async () => {
+	    await alice_client.call( DNA_NAME, COOP_ZOME, "get_group_content_latest", {
+		"group_id": g1_addr,
+		"content_id": new ActionHash(crypto.randomBytes(32)),
+	    });
+	}, "is not in group content" );
+    });
+
+    it("should fail to repair group revision because agent is not an admin", async function () {
+	await expect_reject( async () => {
+	    await bobby_client.call( DNA_NAME, COOP_ZOME, "repair_group_revision", g1a_addr );
+	}, "Only admins of the group revision can repair it" );
+    });
+
+    it("should fail to merge group branches because group is not forked", async function () {
+	await expect_reject( async () => {
+	    await alice_client.call( DNA_NAME, COOP_ZOME, "merge_group_branches", g1_addr );
+	}, "Group is not forked" );
+    });
+
+    it("should fail to update content because member is restricted to other entry types", async function () {
+	const k8_input			= createGroupInput(
+	    [ alice_client.agent_id ],
+	    bobby_client.agent_id,
+	);
+	k8_input.content_type_restrictions = [{
+	    "scope": {
+		"Agent": bobby_client.agent_id,
+	    },
+	    "entry_types": [{
+		"zome_index": 0,
+		"entry_index": 1,
+	    }],
+	}];
+	const k8_addr			= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_group", k8_input );
+	const content_addr		= await alice_client.call( DNA_NAME, GOOD_ZOME, "create_content", createContentInput( alice_client.agent_id, k8_addr, k8_addr ) );
+	const content			= intoStruct( await alice_client.call( DNA_NAME, GOOD_ZOME, "get_content", {
+	    "group_id": k8_addr,
+	    "content_id": content_addr,
+	}), ContentStruct );
+
+	await expect_reject( async () => {
+	    await bobby_client.call( DNA_NAME, GOOD_ZOME, "update_content", {
+		"base": content_addr,
+		"entry": Object.assign( content, {
+		    "text":		"(updated) " + faker.lorem.sentence(),
+		}),
+	    });
+	}, "is not allowed to write entry type" );
+    });
+
     it("should reject group delete", async function () {
 	await expect_reject( async () => {
 	    await alice_client.call( DNA_NAME, EVIL_ZOME, "delete_group", g1_addr );
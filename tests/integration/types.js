@@ -31,6 +31,18 @@ export const EntryCreationActionStruct = {
 export const GroupStruct = {
     "admins":			VecType( AgentPubKey ),
     "members":			VecType( AgentPubKey ),
+    "membership_windows":	VecType({
+	"agent":		AgentPubKey,
+	"valid_from":		OptionType( Number ),
+	"valid_until":		OptionType( Number ),
+    }),
+    "content_type_restrictions":	VecType({
+	"scope":		{},
+	"entry_types":		VecType({
+	    "zome_index":	Number,
+	    "entry_index":	Number,
+	}),
+    }),
 
     "published_at":		Number,
     "last_updated":		Number,
@@ -30,6 +30,15 @@ pub enum EntryTypes {
 
     #[entry_type]
     ArchivedContributionsAnchor(ArchivedContributionsAnchorEntry),
+
+    #[entry_type]
+    KeySuccession(KeySuccessionEntry),
+
+    #[entry_type]
+    GroupKey(GroupKeyEntry),
+
+    #[entry_type]
+    ContentProposal(ContentProposalEntry),
 }
 
 scoped_type_connector!(
@@ -44,6 +53,18 @@ scoped_type_connector!(
     EntryTypesUnit::ArchivedContributionsAnchor,
     EntryTypes::ArchivedContributionsAnchor( ArchivedContributionsAnchorEntry )
 );
+scoped_type_connector!(
+    EntryTypesUnit::KeySuccession,
+    EntryTypes::KeySuccession( KeySuccessionEntry )
+);
+scoped_type_connector!(
+    EntryTypesUnit::GroupKey,
+    EntryTypes::GroupKey( GroupKeyEntry )
+);
+scoped_type_connector!(
+    EntryTypesUnit::ContentProposal,
+    EntryTypes::ContentProposal( ContentProposalEntry )
+);
 
 
 
@@ -55,6 +76,18 @@ pub enum LinkTypes {
     GroupAuthArchive,
     Contribution,
     ContributionUpdate,
+    ContentPosition,
+    ContentTombstone,
+    GroupResignation,
+    KeySuccession,
+    ContributionIndex,
+    GroupDirectory,
+    GroupKey,
+    EncryptionKey,
+    ContentProposal,
+    ContentProposalResolution,
+    GroupMerge,
+    GroupRevision,
 }
 
 impl TryFrom<String> for LinkTypes {
@@ -68,6 +101,18 @@ impl TryFrom<String> for LinkTypes {
                 "GroupAuthArchive" => LinkTypes::GroupAuthArchive,
                 "Contribution" => LinkTypes::Contribution,
                 "ContributionUpdate" => LinkTypes::ContributionUpdate,
+                "ContentPosition" => LinkTypes::ContentPosition,
+                "ContentTombstone" => LinkTypes::ContentTombstone,
+                "GroupResignation" => LinkTypes::GroupResignation,
+                "KeySuccession" => LinkTypes::KeySuccession,
+                "ContributionIndex" => LinkTypes::ContributionIndex,
+                "GroupDirectory" => LinkTypes::GroupDirectory,
+                "GroupKey" => LinkTypes::GroupKey,
+                "EncryptionKey" => LinkTypes::EncryptionKey,
+                "ContentProposal" => LinkTypes::ContentProposal,
+                "ContentProposalResolution" => LinkTypes::ContentProposalResolution,
+                "GroupMerge" => LinkTypes::GroupMerge,
+                "GroupRevision" => LinkTypes::GroupRevision,
                 _ => return Err(guest_error!(format!("Unknown LinkTypes variant: {}", name ))),
             }
         )
@@ -80,9 +125,7 @@ impl<'de> Deserialize<'de> for LinkTypes {
         D: Deserializer<'de>,
     {
         let s: String = Deserialize::deserialize(deserializer)?;
-        Ok(
-            LinkTypes::try_from( s.clone() )
-                .or(Err(serde::de::Error::custom(format!("Unknown LinkTypes variant: {}", s))))?
-        )
+        LinkTypes::try_from( s.clone() )
+            .or(Err(serde::de::Error::custom(format!("Unknown LinkTypes variant: {}", s))))
     }
 }
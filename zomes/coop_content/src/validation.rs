@@ -4,6 +4,9 @@ mod delete_entry;
 mod create_link;
 mod delete_link;
 
+#[cfg(test)]
+mod tests;
+
 use crate::{
     hdi,
     hdi_extensions,
@@ -19,7 +22,17 @@ use hdi_extensions::{
 
 #[hdk_extern]
 fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
-    let result = match op.flattened::<EntryTypes, LinkTypes>()? {
+    validate_flat_op( op.flattened::<EntryTypes, LinkTypes>()? )
+}
+
+
+/// Route a flattened op to its validation logic
+///
+/// Guest errors raised by the validation logic are returned as invalid results.
+pub(crate) fn validate_flat_op(
+    flat_op: FlatOp<EntryTypes, LinkTypes>
+) -> ExternResult<ValidateCallbackResult> {
+    let result = match flat_op {
         FlatOp::StoreRecord(op_record) => match op_record {
             OpRecord::CreateEntry { app_entry, action } =>
                 create_entry::validation( app_entry, action ),
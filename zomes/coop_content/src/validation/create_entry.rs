@@ -1,13 +1,23 @@
+use std::collections::HashSet;
 use crate::{
     hdi,
     hdi_extensions,
+    holo_hash,
     EntryTypes,
+    GroupEntry,
+    KeySuccessionEntry,
+    GroupKeyEntry,
+    X25519_PUB_KEY_LENGTH,
+    XSALSA20_POLY1305_NONCE_LENGTH,
 };
 use hdi::prelude::*;
 use hdi_extensions::{
+    trace_origin,
+    trace_origin_root,
     // Macros
     valid, invalid,
 };
+use holo_hash::AnyLinkableHashPrimitive;
 
 
 pub fn validation(
@@ -28,6 +38,139 @@ pub fn validation(
         EntryTypes::ArchivedContributionsAnchor(_anchor) => {
             valid!()
         },
+        EntryTypes::KeySuccession(succession) => {
+            if succession.predecessor != create.author {
+                invalid!(format!("A key succession can only be made by the predecessor key ({})", succession.predecessor ))
+            }
+
+            if succession.successor == succession.predecessor {
+                invalid!("A key cannot succeed itself".to_string())
+            }
+
+            if succession.group_id != trace_origin_root( &succession.group_rev )?.0 {
+                invalid!(format!("Key succession group ID is not the initial action for the group revision ({})", succession.group_rev ))
+            }
+
+            match &succession.previous {
+                Some(previous_addr) => {
+                    let previous : KeySuccessionEntry = must_get_valid_record( previous_addr.to_owned() )?.try_into()?;
+
+                    if previous.group_id != succession.group_id || previous.successor != succession.predecessor {
+                        invalid!(format!("The previous key succession ({}) must make the predecessor a successor in the same group", previous_addr ))
+                    }
+                },
+                None => {
+                    let group : GroupEntry = must_get_valid_record( succession.group_rev.to_owned() )?.try_into()?;
+
+                    if !group.is_contributor( &succession.predecessor ) {
+                        invalid!(format!("The predecessor key must be a contributor of the group revision ({})", succession.group_rev ))
+                    }
+
+                    if !group.is_contributor_at( &succession.predecessor, &create.timestamp ) {
+                        invalid!(format!("The predecessor key is outside of their membership window in the group revision ({})", succession.group_rev ))
+                    }
+                },
+            }
+
+            valid!()
+        },
+        EntryTypes::GroupKey(group_key) => {
+            if group_key.group_id != trace_origin_root( &group_key.group_rev )?.0 {
+                invalid!(format!("Group key group ID is not the initial action for the group revision ({})", group_key.group_rev ))
+            }
+
+            let group : GroupEntry = must_get_valid_record( group_key.group_rev.to_owned() )?.try_into()?;
+
+            if !group.is_admin( &create.author ) {
+                invalid!(format!("A group key can only be made by an admin of the group revision ({})", group_key.group_rev ))
+            }
+
+            if group_key.sender_key.len() != X25519_PUB_KEY_LENGTH {
+                invalid!(format!("Group key sender key must be {} bytes", X25519_PUB_KEY_LENGTH ))
+            }
+
+            // Only contributors can receive a share (contributors without an encryption key are pending)
+            let recipients = group_key.recipients();
+            let contributors : HashSet<AgentPubKey> = group.contributors().into_iter().collect();
+
+            if recipients.len() != group_key.shares.len() {
+                invalid!("A group key cannot have more than one share per agent".to_string())
+            }
+
+            if !recipients.is_subset( &contributors ) {
+                invalid!(format!("A group key can only be shared with contributors of the group revision ({})", group_key.group_rev ))
+            }
+
+            for share in group_key.shares.iter() {
+                if share.recipient_key.len() != X25519_PUB_KEY_LENGTH || share.nonce.len() != XSALSA20_POLY1305_NONCE_LENGTH {
+                    invalid!(format!("Group key share for agent ({}) has a malformed recipient key or nonce", share.agent ))
+                }
+            }
+
+            match &group_key.previous {
+                Some(previous_addr) => {
+                    let previous : GroupKeyEntry = must_get_valid_record( previous_addr.to_owned() )?.try_into()?;
+
+                    if previous.group_id != group_key.group_id {
+                        invalid!(format!("The previous group key ({}) must belong to the same group", previous_addr ))
+                    }
+
+                    // The key must rotate when anyone who held the previous key loses access
+                    let revoked = previous.recipients().iter()
+                        .any(|agent| !recipients.contains( agent ) );
+
+                    if revoked && group_key.epoch != previous.epoch + 1 {
+                        invalid!(format!("A group key must rotate to epoch {} when a contributor is removed", previous.epoch + 1 ))
+                    }
+
+                    if !revoked && group_key.epoch != previous.epoch && group_key.epoch != previous.epoch + 1 {
+                        invalid!(format!("A group key epoch must be {} or {}", previous.epoch, previous.epoch + 1 ))
+                    }
+                },
+                None => {
+                    if group_key.epoch != 0 {
+                        invalid!("The first group key must be epoch 0".to_string())
+                    }
+                },
+            }
+
+            valid!()
+        },
+        EntryTypes::ContentProposal(proposal) => {
+            if proposal.group_id != trace_origin_root( &proposal.group_id )?.0 {
+                invalid!(format!("Content proposal group ID ({}) must be the initial action of a group", proposal.group_id ))
+            }
+
+            let _group : GroupEntry = must_get_valid_record( proposal.group_id.to_owned() )?.try_into()?;
+
+            if let (
+                AnyLinkableHashPrimitive::Action(id_addr),
+                AnyLinkableHashPrimitive::Action(rev_addr)
+            ) = (proposal.content_id.clone().into_primitive(), proposal.content_rev.clone().into_primitive()) {
+                if id_addr != trace_origin_root( &rev_addr )?.0 {
+                    invalid!(format!("Content proposal content ID is not the root of the content revision: {} / {}", id_addr, rev_addr ))
+                }
+            }
+
+            // An action proposal must be an update that descends from the content revision
+            if let Some(proposed_addr) = proposal.proposed_rev.clone().into_action_hash() {
+                let rev_addr = match proposal.content_rev.clone().into_action_hash() {
+                    Some(addr) => addr,
+                    None => invalid!(format!("Content proposal with an action revision must have an action content revision: {}", proposal.content_rev )),
+                };
+                let history = trace_origin( &proposed_addr )?;
+
+                if !history.iter().skip(1).any(|(addr, _)| addr == &rev_addr ) {
+                    invalid!(format!("Proposed revision ({}) is not an update of the content revision ({})", proposed_addr, rev_addr ))
+                }
+
+                if history.last().map(|(addr, _)| AnyLinkableHash::from( addr.to_owned() ) ).as_ref() != Some( &proposal.content_id ) {
+                    invalid!(format!("Proposed revision ({}) is not a revision of the content ID ({})", proposed_addr, proposal.content_id ))
+                }
+            }
+
+            valid!()
+        },
         // _ => invalid!(format!("Create validation not implemented for entry type: {:#?}", create.entry_type )),
     }
 }
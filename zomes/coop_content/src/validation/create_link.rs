@@ -5,24 +5,73 @@ use crate::{
     LinkTypes,
     GroupEntry,
     ContributionAnchors,
+    ContributionTag,
+    ContributionUpdateTag,
+    ContentPositionTag,
+    ContentTombstoneTag,
+    KeySuccessionEntry,
+    GroupDirectoryTag,
+    GroupKeyEntry,
+    group_directory_group_anchor,
+    X25519_PUB_KEY_LENGTH,
+    ContentProposalEntry,
+    ContentProposalResolutionTag,
+    split_governing_revision,
 };
 use hdi::prelude::*;
 use hdi_extensions::{
+    trace_origin,
     trace_origin_root,
     summon_app_entry,
     verify_app_entry_struct,
-    AnyLinkableHashTransformer,
     // Macros
     valid, invalid, guest_error,
 };
 use holo_hash::AnyLinkableHashPrimitive;
 
 
+/// Get the time that the author of a link resigned from the given group (earlier in their chain)
+///
+/// Resignation links are not reachable from a group revision in validation, so the author's own
+/// chain is searched instead.  The earliest resignation is used.
+fn author_resignation(
+    create: &CreateLink,
+    group_id: &ActionHash,
+) -> ExternResult<Option<Timestamp>> {
+    let activity = must_get_agent_activity( create.author.to_owned(), ChainFilter::new( create.prev_action.to_owned() ) )?;
+    let mut resigned_at : Option<Timestamp> = None;
+
+    for item in activity {
+        let link = match item.action.hashed.content {
+            Action::CreateLink(link) => link,
+            _ => continue,
+        };
+
+        if LinkTypes::from_type( link.zome_index, link.link_type )? != Some(LinkTypes::GroupResignation) {
+            continue;
+        }
+
+        let group_rev = match link.base_address.into_action_hash() {
+            Some(addr) => addr,
+            None => continue,
+        };
+
+        if &trace_origin_root( &group_rev )?.0 == group_id {
+            resigned_at = Some( match resigned_at {
+                Some(timestamp) => std::cmp::min( timestamp, link.timestamp ),
+                None => link.timestamp,
+            });
+        }
+    }
+
+    Ok( resigned_at )
+}
+
 fn validate_content_link_base(
     base: &AnyLinkableHash,
     create: &CreateLink,
-) -> ExternResult<()> {
-    let anchor : ContributionAnchors = summon_app_entry( &base )?;
+) -> ExternResult<ContributionAnchors> {
+    let anchor : ContributionAnchors = summon_app_entry( base )?;
 
     if anchor.is_archive() {
         let group : GroupEntry = must_get_valid_record( anchor.group().to_owned() )?.try_into()?;
@@ -33,9 +82,106 @@ fn validate_content_link_base(
         Err(guest_error!(format!("Creating a link based on an auth anchor can only be made by the matching agent ({})", anchor.author() )))?
     }
 
+    Ok( anchor )
+}
+
+/// Check the group revision that governs a link on a contributions anchor
+///
+/// The revision must belong to the anchor's group.  On an agent's own anchor, the link must be
+/// within the agent's membership window in that revision and cannot come after the agent resigned
+/// from the group, unless the revision re-added them since.
+fn validate_governing_revision(
+    anchor: &ContributionAnchors,
+    group_rev: &ActionHash,
+    create: &CreateLink,
+) -> ExternResult<()> {
+    let group_id = trace_origin_root( anchor.group() )?.0;
+    let history = trace_origin( group_rev )?;
+
+    if history.last().map(|(addr, _)| addr ) != Some( &group_id ) {
+        Err(guest_error!(format!("Governing group revision ({}) is not a revision of the anchor's group ({})", group_rev, group_id )))?
+    }
+
+    // Archived copies keep the revision of their original link
+    if anchor.is_archive() {
+        return Ok(());
+    }
+
+    let group : GroupEntry = must_get_valid_record( group_rev.to_owned() )?.try_into()?;
+
+    // Contributors added in later revisions are bounded by the resolvers instead
+    if group.is_contributor( &create.author ) && !group.is_contributor_at( &create.author, &create.timestamp ) {
+        Err(guest_error!(format!("Creating a link based on an auth anchor must be within the agent's membership window in the governing group revision ({})", group_rev )))?
+    }
+
+    // A resignation cannot be undone by an admin keeping the member in later revisions
+    if let Some(resigned_at) = author_resignation( create, &group_id )? {
+        let mut readded = false;
+
+        if group.is_contributor( &create.author ) {
+            for (rev_addr, _) in history.iter().take_while(|(_, action)| action.timestamp() > resigned_at ) {
+                let revision : GroupEntry = must_get_valid_record( rev_addr.to_owned() )?.try_into()?;
+
+                if !revision.is_contributor( &create.author ) {
+                    readded = true;
+                    break;
+                }
+            }
+        }
+
+        if !readded {
+            Err(guest_error!(format!("Creating a link based on an auth anchor cannot be done after the agent resigned from the group ({}) at {}", group_id, resigned_at )))?
+        }
+    }
+
     Ok(())
 }
 
+/// Split the governing group revision from a contributions anchor link tag and check it
+///
+/// Returns the tag payload and the governing revision.
+fn validate_governed_tag<'a>(
+    anchor: &ContributionAnchors,
+    tag: &'a LinkTag,
+    create: &CreateLink,
+) -> ExternResult<(&'a [u8], ActionHash)> {
+    let (payload, group_rev) = split_governing_revision( &tag.0 )
+        .map_err(|message| guest_error!(message) )?;
+
+    validate_governing_revision( anchor, &group_rev, create )?;
+
+    Ok( (payload, group_rev) )
+}
+
+/// Get the original link of an archived copy
+///
+/// The original must be the same type of link, based on the archive agent's auth anchor in the
+/// archive's group.
+fn must_get_original_link(
+    original_addr: &ActionHash,
+    link_type: LinkTypes,
+    anchor: &ContributionAnchors,
+) -> ExternResult<CreateLink> {
+    let original = match must_get_valid_record( original_addr.to_owned() )?.action() {
+        Action::CreateLink(action) => action.to_owned(),
+        _ => Err(guest_error!(format!("Archived {:?} original ({}) is not a create link action", link_type, original_addr )))?,
+    };
+
+    if LinkTypes::from_type( original.zome_index, original.link_type )? != Some(link_type) {
+        Err(guest_error!(format!("Archived {:?} original ({}) is not a {:?} link", link_type, original_addr, link_type )))?
+    }
+
+    let original_anchor : ContributionAnchors = summon_app_entry( &original.base_address )?;
+
+    if original_anchor.is_archive()
+        || original_anchor.author() != anchor.author()
+        || original_anchor.group() != &trace_origin_root( anchor.group() )?.0 {
+        Err(guest_error!(format!("Archived {:?} original ({}) must be based on the anchor agent's auth anchor in the same group", link_type, original_addr )))?
+    }
+
+    Ok( original )
+}
+
 fn validate_anchor_link_base(
     base: &AnyLinkableHash,
     target: &AnyLinkableHash,
@@ -66,45 +212,130 @@ pub fn validation(
 ) -> ExternResult<ValidateCallbackResult> {
     match link_type {
         LinkTypes::Contribution => {
-            validate_content_link_base( &base_address, &create )?;
+            let anchor = validate_content_link_base( &base_address, &create )?;
+            let (payload, _) = validate_governed_tag( &anchor, &tag, &create )?;
+
+            // An empty payload is allowed for contributions that do not declare a kind
+            if !payload.is_empty() {
+                if let Err(message) = ContributionTag::from_bytes( payload ) {
+                    invalid!(format!("Contribution link has malformed tag: {}", message ))
+                }
+            }
 
             valid!()
         },
         LinkTypes::ContributionUpdate => {
-            validate_content_link_base( &base_address, &create )?;
+            let anchor = validate_content_link_base( &base_address, &create )?;
+            let (payload, _) = validate_governed_tag( &anchor, &tag, &create )?;
 
-            let tag_str = match String::from_utf8( tag.into_inner() ) {
-                Ok(text) => text,
-                Err(err) => invalid!(format!("Contribution update link tag must be a UTF8 string: {}", err )),
+            let ContributionUpdateTag { content_id, content_rev } = match ContributionUpdateTag::from_bytes( payload ) {
+                Ok(update_tag) => update_tag,
+                Err(message) => invalid!(format!("Contribution update link has malformed tag: {}", message )),
             };
 
-            if !tag_str.contains(":") {
-                invalid!(format!("Contribution update link has malformed tag: {}", tag_str ))
+            // Is this check necessary?  Can't we just let group contributors define any pointers
+            // that they want?
+            if let (
+                AnyLinkableHashPrimitive::Action(id_addr),
+                AnyLinkableHashPrimitive::Action(rev_addr)
+            ) = (content_id.into_primitive(), content_rev.clone().into_primitive()) {
+                if id_addr != trace_origin_root( &rev_addr )?.0 {
+                    invalid!(format!("Tag parts do not match; Contribution update link tag ID is not the root of the tag revision: {} / {}", id_addr, rev_addr ))
+                }
+            }
+
+            // An action target must be an update that descends from the tag revision
+            if let Some(target_addr) = target_address.into_action_hash() {
+                let rev_addr = match content_rev.clone().into_action_hash() {
+                    Some(addr) => addr,
+                    None => invalid!(format!("Contribution update link with an action target must have an action tag revision: {}", content_rev )),
+                };
+                let history = trace_origin( &target_addr )?;
+
+                if !history.iter().skip(1).any(|(addr, _)| addr == &rev_addr ) {
+                    invalid!(format!("Contribution update link target ({}) is not an update of the tag revision ({})", target_addr, rev_addr ))
+                }
             }
 
-            let (tag_id, tag_rev) = match tag_str.split_once(":") {
-                Some(parts) => parts,
-                None => invalid!(format!("Contribution update link has malformed tag: {}", tag_str )),
+            valid!()
+        },
+        LinkTypes::ContentPosition => {
+            let anchor = validate_content_link_base( &base_address, &create )?;
+            let (payload, group_rev) = validate_governed_tag( &anchor, &tag, &create )?;
+
+            let position = match ContentPositionTag::from_bytes( payload ) {
+                Ok(position) => position,
+                Err(message) => invalid!(format!("Content position link has malformed tag: {}", message )),
             };
 
-            let content_id = match AnyLinkableHash::try_from_string( tag_id ) {
-                Ok(addr) => addr,
-                Err(err) => invalid!(format!("Invalid tag part 1: {}", err )),
+            if !anchor.is_archive() {
+                if position.original.is_some() {
+                    invalid!(format!("Content position links based on an auth anchor cannot reference an original link"))
+                }
+
+                valid!()
+            }
+
+            // Archived copies keep the time of the original move, so they must match it
+            let original_addr = match position.original {
+                Some(addr) => addr,
+                None => invalid!(format!("Content position links based on an auth archive anchor must reference the original link")),
             };
-            let content_rev = match AnyLinkableHash::try_from_string( tag_rev ) {
-                Ok(addr) => addr,
-                Err(err) => invalid!(format!("Invalid tag part 2: {}", err )),
+            let original = must_get_original_link( &original_addr, LinkTypes::ContentPosition, &anchor )?;
+            let (original_position, original_rev) = match split_governing_revision( &original.tag.0 )
+                .and_then(|(payload, rev)| Ok( (ContentPositionTag::from_bytes( payload )?, rev) ) ) {
+                Ok(parts) => parts,
+                Err(message) => invalid!(format!("Archived content position original has malformed tag: {}", message )),
             };
 
-            // Is this check necessary?  Can't we just let group contributors define any pointers
-            // that they want?
-            if let (
-                AnyLinkableHashPrimitive::Action(id_addr),
-                AnyLinkableHashPrimitive::Action(rev_addr)
-            ) = (content_id.into_primitive(), content_rev.into_primitive()) {
-                if id_addr != trace_origin_root( &rev_addr )?.0 {
-                    invalid!(format!("Tag parts do not match; Contribution update link tag ID is not the root of the tag revision: {}", tag_str ))
+            if original.target_address != target_address
+                || original_rev != group_rev
+                || original_position.collection != position.collection
+                || original_position.key != position.key {
+                invalid!(format!("Archived content position link must match its original link ({})", original_addr ))
+            }
+
+            valid!()
+        },
+        LinkTypes::ContentTombstone => {
+            let anchor = validate_content_link_base( &base_address, &create )?;
+            let (payload, group_rev) = validate_governed_tag( &anchor, &tag, &create )?;
+
+            let tombstone = match ContentTombstoneTag::from_bytes( payload ) {
+                Ok(tombstone) => tombstone,
+                Err(message) => invalid!(format!("Content tombstone link has malformed tag: {}", message )),
+            };
+
+            if !anchor.is_archive() {
+                if tombstone.original.is_some() {
+                    invalid!(format!("Content tombstone links based on an auth anchor cannot reference an original link"))
+                }
+
+                if tombstone.timestamp > create.timestamp {
+                    invalid!(format!("Content tombstone tag timestamp ({}) cannot be after the link timestamp ({})", tombstone.timestamp, create.timestamp ))
                 }
+
+                valid!()
+            }
+
+            // Archived copies keep the original timestamp, so they must match the original link
+            let original_addr = match tombstone.original {
+                Some(addr) => addr,
+                None => invalid!(format!("Archived content tombstone links must reference the original link")),
+            };
+            let original = must_get_original_link( &original_addr, LinkTypes::ContentTombstone, &anchor )?;
+
+            let (original_tag, original_rev) = match split_governing_revision( &original.tag.0 )
+                .and_then(|(payload, rev)| Ok( (ContentTombstoneTag::from_bytes( payload )?, rev) ) ) {
+                Ok(parts) => parts,
+                Err(message) => invalid!(format!("Archived content tombstone original has malformed tag: {}", message )),
+            };
+
+            if original.target_address != target_address
+                || original_rev != group_rev
+                || original_tag.deleted != tombstone.deleted
+                || original_tag.timestamp != tombstone.timestamp {
+                invalid!(format!("Archived content tombstone link must match its original link ({})", original_addr ))
             }
 
             valid!()
@@ -133,6 +364,254 @@ pub fn validation(
         LinkTypes::GroupAuthArchive => {
             validate_anchor_link_base( &base_address, &target_address, &create )?;
 
+            valid!()
+        },
+        LinkTypes::KeySuccession => {
+            let anchor : ContributionAnchors = summon_app_entry( &base_address )?;
+
+            if anchor.is_archive() {
+                invalid!(format!("Key succession links cannot be based on an auth archive anchor"))
+            }
+
+            validate_content_link_base( &base_address, &create )?;
+
+            let succession : KeySuccessionEntry = summon_app_entry( &target_address )?;
+
+            if &succession.predecessor != anchor.author() || &succession.group_id != anchor.group() {
+                invalid!(format!("Key succession link target must be a succession from the anchor agent in the anchor's group"))
+            }
+
+            validate_governing_revision( &anchor, &succession.group_rev, &create )?;
+
+            let group : GroupEntry = must_get_valid_record( succession.group_rev.to_owned() )?.try_into()?;
+
+            if succession.previous.is_none() && !group.is_contributor_at( &succession.predecessor, &create.timestamp ) {
+                invalid!(format!("The anchor agent is outside of their membership window in the group revision ({})", succession.group_rev ))
+            }
+
+            valid!()
+        },
+        LinkTypes::ContributionIndex => {
+            // Index target should be the author's contributions anchor
+            let anchor : ContributionAnchors = summon_app_entry( &target_address )?;
+
+            if anchor.is_archive() || anchor.author() != &create.author {
+                invalid!(format!("Contribution index link target must be the author's contributions anchor"))
+            }
+
+            // Index tag should be the address of the matching contribution link
+            if tag.0.len() != 39 {
+                invalid!(format!("Contribution index link tag must be a 39 byte contribution link address; not {} bytes", tag.0.len() ))
+            }
+
+            let contribution_addr = match ActionHash::from_raw_39( tag.0.to_owned() ) {
+                Ok(addr) => addr,
+                Err(err) => invalid!(format!("Contribution index link tag must be a contribution link address: {}", err )),
+            };
+            let contribution = match must_get_valid_record( contribution_addr.to_owned() )?.action() {
+                Action::CreateLink(action) => action.to_owned(),
+                _ => invalid!(format!("Contribution index link tag ({}) is not a create link action", contribution_addr )),
+            };
+
+            if LinkTypes::from_type( contribution.zome_index, contribution.link_type )? != Some(LinkTypes::Contribution) {
+                invalid!(format!("Contribution index link tag ({}) is not a contribution link", contribution_addr ))
+            }
+
+            if contribution.author != create.author
+                || contribution.base_address != target_address
+                || contribution.target_address != base_address {
+                invalid!(format!("Contribution index link must reverse a contribution link by the same author ({})", contribution_addr ))
+            }
+
+            valid!()
+        },
+        LinkTypes::GroupDirectory => {
+            let listing = match GroupDirectoryTag::from_bytes( &tag.0 ) {
+                Ok(listing) => listing,
+                Err(message) => invalid!(format!("Group directory link has malformed tag: {}", message )),
+            };
+
+            // Directory target should be the group ID of the listed revision
+            let group_id = match target_address.clone().into_action_hash() {
+                Some(hash) => hash,
+                None => invalid!(format!("Group directory link target must be a group ID; not '{}'", target_address )),
+            };
+
+            if trace_origin_root( &listing.group_rev )?.0 != group_id {
+                invalid!(format!("Group directory link tag revision ({}) is not a revision of the target group ({})", listing.group_rev, group_id ))
+            }
+
+            let group : GroupEntry = must_get_valid_record( listing.group_rev.to_owned() )?.try_into()?;
+
+            if !group.is_admin( &create.author ) {
+                invalid!(format!("Only admins of the group can list it in the group directory"))
+            }
+
+            // Directory base should be the listing's name shard, one of its tag anchors or the
+            // group's own anchor
+            let is_listing_anchor = listing.anchors()?.into_iter()
+                .chain([ group_directory_group_anchor( &group_id )? ])
+                .any(|anchor| AnyLinkableHash::from( anchor ) == base_address );
+
+            if !is_listing_anchor {
+                invalid!(format!("Group directory link base must be the name, tag or group anchor of the listing"))
+            }
+
+            valid!()
+        },
+        LinkTypes::GroupKey => {
+            // Group key base should be the group ID of the key
+            let group_key : GroupKeyEntry = summon_app_entry( &target_address )?;
+
+            if AnyLinkableHash::from( group_key.group_id.to_owned() ) != base_address {
+                invalid!(format!("Group key link base must be the key's group ID ({})", group_key.group_id ))
+            }
+
+            let group : GroupEntry = must_get_valid_record( group_key.group_rev.to_owned() )?.try_into()?;
+
+            if !group.is_admin( &create.author ) {
+                invalid!(format!("Only admins of the group can link a group key"))
+            }
+
+            valid!()
+        },
+        LinkTypes::EncryptionKey => {
+            // Encryption key links go from an agent to itself with the X25519 key as the tag
+            if base_address != AnyLinkableHash::from( create.author.to_owned() ) || target_address != base_address {
+                invalid!(format!("Encryption key links can only be made from and to the author's agent pubkey"))
+            }
+
+            if tag.0.len() != X25519_PUB_KEY_LENGTH {
+                invalid!(format!("Encryption key link tag must be a {} byte X25519 public key", X25519_PUB_KEY_LENGTH ))
+            }
+
+            valid!()
+        },
+        LinkTypes::ContentProposal => {
+            // Proposal target should be a proposal made by the link author for the base group
+            let proposal_addr = match target_address.clone().into_action_hash() {
+                Some(hash) => hash,
+                None => invalid!(format!("Content proposal link target must be a proposal action; not '{}'", target_address )),
+            };
+            let record = must_get_valid_record( proposal_addr )?;
+
+            if record.action().author() != &create.author {
+                invalid!(format!("Content proposal links can only be made by the proposal author"))
+            }
+
+            let proposal : ContentProposalEntry = record.try_into()?;
+
+            if AnyLinkableHash::from( proposal.group_id.to_owned() ) != base_address {
+                invalid!(format!("Content proposal link base must be the proposal's group ID ({})", proposal.group_id ))
+            }
+
+            valid!()
+        },
+        LinkTypes::ContentProposalResolution => {
+            let resolution = match ContentProposalResolutionTag::from_bytes( &tag.0 ) {
+                Ok(resolution) => resolution,
+                Err(message) => invalid!(format!("Content proposal resolution link has malformed tag: {}", message )),
+            };
+            let proposal : ContentProposalEntry = summon_app_entry( &base_address )?;
+
+            if target_address != AnyLinkableHash::from( create.author.to_owned() ) {
+                invalid!(format!("Content proposal resolution link target must be the resolver's agent pubkey"))
+            }
+
+            if trace_origin_root( &resolution.group_rev )?.0 != proposal.group_id {
+                invalid!(format!("Content proposal resolution tag revision ({}) is not a revision of the proposal's group ({})", resolution.group_rev, proposal.group_id ))
+            }
+
+            let group : GroupEntry = must_get_valid_record( resolution.group_rev.to_owned() )?.try_into()?;
+
+            if !group.is_contributor_at( &create.author, &create.timestamp ) {
+                invalid!(format!("Only contributors of the group can resolve a content proposal"))
+            }
+
+            valid!()
+        },
+        LinkTypes::GroupMerge => {
+            // Merge base should be a branch head that the merge revision does not descend from
+            let head_addr = match base_address.clone().into_action_hash() {
+                Some(hash) => hash,
+                None => invalid!(format!("Group merge link base must be a group revision; not '{}'", base_address )),
+            };
+            verify_app_entry_struct::<GroupEntry>( &base_address )?;
+
+            // Merge target should be a group revision made by the link author
+            let merge_addr = match target_address.clone().into_action_hash() {
+                Some(hash) => hash,
+                None => invalid!(format!("Group merge link target must be a group revision; not '{}'", target_address )),
+            };
+            let merge_record = must_get_valid_record( merge_addr.to_owned() )?;
+
+            if merge_record.action().author() != &create.author {
+                invalid!(format!("Group merge links can only be made by the author of the merge revision"))
+            }
+
+            let _ : GroupEntry = merge_record.try_into()?;
+
+            let merge_history = trace_origin( &merge_addr )?;
+
+            if merge_history.iter().any(|(addr, _)| addr == &head_addr ) {
+                invalid!(format!("Group merge link base ({}) is an ancestor of the merge revision", head_addr ))
+            }
+
+            let group_id = match merge_history.last() {
+                Some((addr, _)) => addr.to_owned(),
+                None => invalid!(format!("Group merge revision ({}) has no origin", merge_addr )),
+            };
+
+            if trace_origin_root( &head_addr )?.0 != group_id {
+                invalid!(format!("Group merge link base ({}) is not a revision of the merge revision's group", head_addr ))
+            }
+
+            valid!()
+        },
+        LinkTypes::GroupRevision => {
+            // Revision index base should be the group ID
+            let group_id = match base_address.clone().into_action_hash() {
+                Some(hash) => hash,
+                None => invalid!(format!("Group revision link base must be a group ID; not '{}'", base_address )),
+            };
+
+            // Revision index target should be a revision of the group made by the link author
+            let rev_addr = match target_address.clone().into_action_hash() {
+                Some(hash) => hash,
+                None => invalid!(format!("Group revision link target must be a group revision; not '{}'", target_address )),
+            };
+            let record = must_get_valid_record( rev_addr.to_owned() )?;
+
+            if record.action().author() != &create.author {
+                invalid!(format!("Group revision links can only be made by the author of the revision"))
+            }
+
+            let _ : GroupEntry = record.try_into()?;
+
+            if trace_origin_root( &rev_addr )?.0 != group_id {
+                invalid!(format!("Group revision link target ({}) is not a revision of the base group ({})", rev_addr, group_id ))
+            }
+
+            valid!()
+        },
+        LinkTypes::GroupResignation => {
+            // Resignation base should be a group revision
+            let group : GroupEntry = summon_app_entry( &base_address )?;
+
+            // Resignation target should be the resigning agent
+            let agent_pubkey = match target_address.clone().into_agent_pub_key() {
+                Some(hash) => hash,
+                None => invalid!(format!("Group resignation link target must be an agent pubkey; not '{}'", target_address )),
+            };
+
+            if agent_pubkey != create.author {
+                invalid!(format!("A group resignation can only be made by the resigning agent ({})", agent_pubkey ))
+            }
+
+            if !group.is_member( &create.author ) {
+                invalid!(format!("Only members of the base group revision can resign; admins must update the group"))
+            }
+
             valid!()
         },
     }
@@ -8,14 +8,14 @@ use hdi_extensions::{
     summon_create_action,
     detect_app_entry_unit,
     // Macros
-    invalid,
+    valid, invalid,
 };
 
 
 pub fn validation(
     original_action_hash: ActionHash,
     _original_entry_hash: EntryHash,
-    _delete: Delete
+    delete: Delete
 ) -> ExternResult<ValidateCallbackResult> {
     let create = summon_create_action( &original_action_hash )?;
 
@@ -29,6 +29,19 @@ pub fn validation(
         EntryTypesUnit::ArchivedContributionsAnchor => {
             invalid!("Anchors are required for the continuity of group content evolution".to_string())
         },
+        EntryTypesUnit::KeySuccession => {
+            invalid!("Key successions are required for the continuity of group content evolution".to_string())
+        },
+        EntryTypesUnit::GroupKey => {
+            invalid!("Group keys are required to decrypt group content".to_string())
+        },
+        EntryTypesUnit::ContentProposal => {
+            if create.author != delete.author {
+                invalid!("A content proposal can only be withdrawn by its author".to_string())
+            }
+
+            valid!()
+        },
         // entry_type_unit => invalid!(format!("Delete validation not implemented for entry type: {:?}", entry_type_unit )),
     }
 }
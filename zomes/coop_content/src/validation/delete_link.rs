@@ -4,6 +4,7 @@ use crate::{
     LinkTypes,
     GroupEntry,
     ContributionAnchors,
+    GroupDirectoryTag,
 };
 use hdi::prelude::*;
 use hdi_extensions::{
@@ -29,7 +30,7 @@ pub fn validation(
     };
 
     match link_type {
-        LinkTypes::Contribution | LinkTypes::ContributionUpdate => {
+        LinkTypes::Contribution | LinkTypes::ContributionUpdate | LinkTypes::ContentPosition => {
             // Deletion is valid when
             // - the base is an archive anchor, if the author is an admin
             // - the base is an auth anchor, if the author is the matching anchor agent
@@ -60,6 +61,69 @@ pub fn validation(
 
             valid!()
         },
+        LinkTypes::ContributionIndex => {
+            // These can be deleted by the original author of the link (eg. along with the
+            // contribution link)
+            if create_link.author != delete.author {
+                invalid!(format!("A contribution index link can only be deleted by the author who created it ({})", create_link.author ))
+            }
+
+            valid!()
+        },
+        LinkTypes::GroupDirectory => {
+            // Only admins of the listed group revision can delist it
+            let listing = match GroupDirectoryTag::from_bytes( &create_link.tag.0 ) {
+                Ok(listing) => listing,
+                Err(message) => invalid!(format!("Group directory link has malformed tag: {}", message )),
+            };
+            let group : GroupEntry = must_get_valid_record( listing.group_rev.to_owned() )?.try_into()?;
+
+            if !group.is_admin( &delete.author ) {
+                invalid!(format!("A group directory link can only be deleted by an admin of the listed group revision ({})", listing.group_rev ))
+            }
+
+            valid!()
+        },
+        LinkTypes::ContentTombstone => {
+            // The deletion history of content must be preserved; an undelete is registered as a
+            // new tombstone link instead.
+            invalid!(format!("Once created, content tombstone links cannot be deleted"))
+        },
+        LinkTypes::KeySuccession => {
+            invalid!(format!("Once created, key succession links cannot be deleted"))
+        },
+        LinkTypes::GroupKey => {
+            invalid!(format!("Once created, group key links cannot be deleted"))
+        },
+        LinkTypes::ContentProposal => {
+            // A proposal can be withdrawn by its author
+            if create_link.author != delete.author {
+                invalid!(format!("Content proposal links can only be deleted by their author"))
+            }
+
+            valid!()
+        },
+        LinkTypes::ContentProposalResolution => {
+            invalid!(format!("Once created, content proposal resolution links cannot be deleted"))
+        },
+        LinkTypes::EncryptionKey => {
+            // An agent can replace their encryption key
+            if create_link.author != delete.author {
+                invalid!(format!("Encryption key links can only be deleted by their author"))
+            }
+
+            valid!()
+        },
+        LinkTypes::GroupMerge => {
+            invalid!(format!("Once created, group merge links cannot be deleted"))
+        },
+        LinkTypes::GroupRevision => {
+            invalid!(format!("Once created, group revision links cannot be deleted"))
+        },
+        LinkTypes::GroupResignation => {
+            // A resignation remains in effect until an admin formalises it with a group update
+            invalid!(format!("Once created, group resignation links cannot be deleted"))
+        },
         LinkTypes::GroupAuth | LinkTypes::GroupAuthArchive => {
             // Never allowed because the way to remove members is by updating the group.  Once a
             // GroupAuth link is successfully made, it must be valid forever.
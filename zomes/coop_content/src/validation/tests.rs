@@ -0,0 +1,7 @@
+mod fixtures;
+
+mod create_entry;
+mod update_entry;
+mod delete_entry;
+mod create_link;
+mod delete_link;
@@ -0,0 +1,303 @@
+use crate::{
+    hdi,
+    EntryTypes,
+    ContributionsAnchorEntry,
+    ArchivedContributionsAnchorEntry,
+    ContentProposalEntry,
+    MembershipWindow,
+};
+use hdi::prelude::*;
+use super::super::validate_flat_op;
+use super::fixtures::*;
+
+
+#[test]
+fn group_create_by_admin_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let group = EntryTypes::Group( group_entry( &[ &alice ], &[ &bobby ] ) );
+    let record = dht.create_entry( &alice, &group );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_entry_op( group, &record ) ) );
+}
+
+#[test]
+fn group_create_by_non_admin_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let group = EntryTypes::Group( group_entry( &[ &alice ], &[ &bobby ] ) );
+    let record = dht.create_entry( &bobby, &group );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_entry_op( group, &record ) ),
+        "author of a group entry must be an admin",
+    );
+}
+
+#[test]
+fn contributions_anchor_create_has_no_requirements() {
+    let alice = agent(1);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let group_id = ActionHash::from_raw_36( vec![ 0xdb; 36 ] );
+    let anchor = EntryTypes::ContributionsAnchor( ContributionsAnchorEntry( group_id, alice ) );
+    let record = dht.create_entry( &carol, &anchor );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_entry_op( anchor, &record ) ) );
+}
+
+#[test]
+fn archived_contributions_anchor_create_has_no_requirements() {
+    let alice = agent(1);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let group_rev = ActionHash::from_raw_36( vec![ 0xdb; 36 ] );
+    let anchor = EntryTypes::ArchivedContributionsAnchor(
+        ArchivedContributionsAnchorEntry::new( group_rev, alice )
+    );
+    let record = dht.create_entry( &carol, &anchor );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_entry_op( anchor, &record ) ) );
+}
+
+#[test]
+fn key_succession_by_contributor_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let bobby_v2 = agent(12);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let succession = key_succession_entry( &g1, &bobby, &bobby_v2, None );
+    let record = dht.create_entry( &bobby, &succession );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_entry_op( succession, &record ) ) );
+}
+
+#[test]
+fn key_succession_by_other_agent_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let succession = key_succession_entry( &g1, &bobby, &carol, None );
+    let record = dht.create_entry( &carol, &succession );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_entry_op( succession, &record ) ),
+        "can only be made by the predecessor key",
+    );
+}
+
+#[test]
+fn key_succession_from_non_contributor_is_invalid() {
+    let alice = agent(1);
+    let carol = agent(3);
+    let carol_v2 = agent(13);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let succession = key_succession_entry( &g1, &carol, &carol_v2, None );
+    let record = dht.create_entry( &carol, &succession );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_entry_op( succession, &record ) ),
+        "predecessor key must be a contributor",
+    );
+}
+
+#[test]
+fn key_succession_outside_membership_window_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let bobby_v2 = agent(12);
+    let mut dht = FixtureDht::new();
+    let mut group = group_entry( &[ &alice ], &[ &bobby ] );
+    group.membership_windows.push( MembershipWindow {
+        agent: bobby.to_owned(),
+        valid_from: None,
+        valid_until: Some( Timestamp::from_micros( 0 ) ),
+    });
+    let g1 = dht.create_group( &alice, &group );
+    let succession = key_succession_entry( &g1, &bobby, &bobby_v2, None );
+    let record = dht.create_entry( &bobby, &succession );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_entry_op( succession, &record ) ),
+        "outside of their membership window",
+    );
+}
+
+#[test]
+fn chained_key_succession_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let bobby_v2 = agent(12);
+    let bobby_v3 = agent(22);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let first = dht.key_succession( &g1, &bobby, &bobby_v2, None );
+    let succession = key_succession_entry( &g1, &bobby_v2, &bobby_v3, Some( first.action_address().to_owned() ) );
+    let record = dht.create_entry( &bobby_v2, &succession );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_entry_op( succession, &record ) ) );
+}
+
+#[test]
+fn group_key_shared_with_contributors_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let group_key = group_key_entry( g1.action_address(), g1.action_address(), 0, &[ &alice, &bobby ], None );
+    let record = dht.create_entry( &alice, &group_key );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_entry_op( group_key, &record ) ) );
+}
+
+#[test]
+fn group_key_by_member_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let group_key = group_key_entry( g1.action_address(), g1.action_address(), 0, &[ &alice, &bobby ], None );
+    let record = dht.create_entry( &bobby, &group_key );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_entry_op( group_key, &record ) ),
+        "can only be made by an admin",
+    );
+}
+
+#[test]
+fn group_key_missing_a_pending_contributor_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let group_key = group_key_entry( g1.action_address(), g1.action_address(), 0, &[ &alice ], None );
+    let record = dht.create_entry( &alice, &group_key );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_entry_op( group_key, &record ) ) );
+}
+
+#[test]
+fn group_key_shared_with_non_contributor_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let group_key = group_key_entry( g1.action_address(), g1.action_address(), 0, &[ &alice, &bobby, &carol ], None );
+    let record = dht.create_entry( &alice, &group_key );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_entry_op( group_key, &record ) ),
+        "can only be shared with contributors of the group revision",
+    );
+}
+
+#[test]
+fn group_key_without_rotation_after_removal_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let first = dht.create_entry( &alice, &group_key_entry( g1.action_address(), g1.action_address(), 0, &[ &alice, &bobby ], None ) );
+    let g2 = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let group_key = group_key_entry( g1.action_address(), g2.action_address(), 0, &[ &alice ], Some( first.action_address().to_owned() ) );
+    let record = dht.create_entry( &alice, &group_key );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_entry_op( group_key, &record ) ),
+        "must rotate to epoch 1",
+    );
+}
+
+#[test]
+fn group_key_rotation_after_removal_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let first = dht.create_entry( &alice, &group_key_entry( g1.action_address(), g1.action_address(), 0, &[ &alice, &bobby ], None ) );
+    let g2 = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let group_key = group_key_entry( g1.action_address(), g2.action_address(), 1, &[ &alice ], Some( first.action_address().to_owned() ) );
+    let record = dht.create_entry( &alice, &group_key );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_entry_op( group_key, &record ) ) );
+}
+
+#[test]
+fn content_proposal_from_non_member_is_valid() {
+    let alice = agent(1);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let content = dht.create_content( &alice );
+    let proposed = dht.update_content( &carol, &content );
+    let proposal = content_proposal_entry( &g1, &content, &proposed );
+    let record = dht.create_entry( &carol, &proposal );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_entry_op( proposal, &record ) ) );
+}
+
+#[test]
+fn content_proposal_not_updating_the_content_revision_is_invalid() {
+    let alice = agent(1);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let content = dht.create_content( &alice );
+    let other = dht.create_content( &carol );
+    let proposed = dht.update_content( &carol, &other );
+    let proposal = content_proposal_entry( &g1, &content, &proposed );
+    let record = dht.create_entry( &carol, &proposal );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_entry_op( proposal, &record ) ),
+        "is not an update of the content revision",
+    );
+}
+
+#[test]
+fn content_proposal_with_an_entry_content_id_is_invalid() {
+    let alice = agent(1);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let content = dht.create_content( &alice );
+    let proposed = dht.update_content( &carol, &content );
+    let proposal = EntryTypes::ContentProposal( ContentProposalEntry {
+        group_id: g1.action_address().to_owned(),
+        content_id: content.action().entry_hash().unwrap().to_owned().into(),
+        content_rev: content.action_address().to_owned().into(),
+        proposed_rev: proposed.action_address().to_owned().into(),
+    });
+    let record = dht.create_entry( &carol, &proposal );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_entry_op( proposal, &record ) ),
+        "is not a revision of the content ID",
+    );
+}
@@ -0,0 +1,1474 @@
+use crate::{
+    hdi,
+    EntryTypes,
+    LinkTypes,
+    MembershipWindow,
+    ContributionTag,
+    ContributionUpdateTag,
+    ContentPositionTag,
+    ContentTombstoneTag,
+    GroupDirectoryTag,
+    ContentProposalResolutionTag,
+    group_directory_name_anchor,
+    group_directory_tag_anchor,
+    group_directory_group_anchor,
+    GROUP_DIRECTORY_TAG_VERSION,
+};
+use hdi::prelude::*;
+use super::super::validate_flat_op;
+use super::fixtures::*;
+
+
+//
+// Group
+//
+#[test]
+fn group_link_from_matching_agent_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let link = dht.create_link( &alice, alice.to_owned(), g1.action_address().to_owned(), LinkTypes::Group, () );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::Group, &link ) ) );
+}
+
+#[test]
+fn group_link_from_other_agent_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let link = dht.create_link( &bobby, alice.to_owned(), g1.action_address().to_owned(), LinkTypes::Group, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Group, &link ) ),
+        "can only be made by the matching agent",
+    );
+}
+
+#[test]
+fn group_link_with_non_agent_base_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), g1.action_address().to_owned(), LinkTypes::Group, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Group, &link ) ),
+        "base address must be an agent pubkey",
+    );
+}
+
+#[test]
+fn group_link_with_non_group_target_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let link = dht.create_link( &alice, alice.to_owned(), anchor, LinkTypes::Group, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Group, &link ) ),
+        "Could not deserialize",
+    );
+}
+
+
+//
+// Group Auth
+//
+#[test]
+fn group_auth_link_by_admin_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), anchor, LinkTypes::GroupAuth, () );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::GroupAuth, &link ) ) );
+}
+
+#[test]
+fn group_auth_link_by_member_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let link = dht.create_link( &bobby, g1.action_address().to_owned(), anchor, LinkTypes::GroupAuth, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupAuth, &link ) ),
+        "author of a group auth link must be an admin of the base group",
+    );
+}
+
+#[test]
+fn group_auth_link_to_non_contributor_anchor_is_invalid() {
+    let alice = agent(1);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &carol );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), anchor, LinkTypes::GroupAuth, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupAuth, &link ) ),
+        "contributions anchor must match a contributor in the group base",
+    );
+}
+
+#[test]
+fn group_auth_link_with_non_group_base_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let link = dht.create_link( &alice, anchor.to_owned(), anchor, LinkTypes::GroupAuth, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupAuth, &link ) ),
+        "Could not deserialize",
+    );
+}
+
+
+//
+// Group Auth Archive
+//
+#[test]
+fn group_auth_archive_link_by_admin_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), anchor, LinkTypes::GroupAuthArchive, () );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::GroupAuthArchive, &link ) ) );
+}
+
+#[test]
+fn group_auth_archive_link_by_member_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &carol );
+    let link = dht.create_link( &bobby, g1.action_address().to_owned(), anchor, LinkTypes::GroupAuthArchive, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupAuthArchive, &link ) ),
+        "author of a group auth link must be an admin of the base group",
+    );
+}
+
+
+//
+// Group Merge
+//
+#[test]
+fn group_merge_link_from_merge_author_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let g1a = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let g1b = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[ &bobby ] ) ) );
+    let merge = dht.update_entry( &alice, &g1a, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let link = dht.create_link( &alice, g1b.action_address().to_owned(), merge.action_address().to_owned(), LinkTypes::GroupMerge, () );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::GroupMerge, &link ) ) );
+}
+
+#[test]
+fn group_merge_link_from_other_agent_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice, &bobby ], &[] ) );
+    let g1a = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice, &bobby ], &[] ) ) );
+    let g1b = dht.update_entry( &bobby, &g1, &EntryTypes::Group( group_entry( &[ &alice, &bobby ], &[] ) ) );
+    let merge = dht.update_entry( &alice, &g1a, &EntryTypes::Group( group_entry( &[ &alice, &bobby ], &[] ) ) );
+    let link = dht.create_link( &bobby, g1b.action_address().to_owned(), merge.action_address().to_owned(), LinkTypes::GroupMerge, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupMerge, &link ) ),
+        "can only be made by the author of the merge revision",
+    );
+}
+
+#[test]
+fn group_merge_link_from_ancestor_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g1a = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let merge = dht.update_entry( &alice, &g1a, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let link = dht.create_link( &alice, g1a.action_address().to_owned(), merge.action_address().to_owned(), LinkTypes::GroupMerge, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupMerge, &link ) ),
+        "is an ancestor of the merge revision",
+    );
+}
+
+#[test]
+fn group_merge_link_from_other_group_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g2 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g1a = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let link = dht.create_link( &alice, g2.action_address().to_owned(), g1a.action_address().to_owned(), LinkTypes::GroupMerge, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupMerge, &link ) ),
+        "is not a revision of the merge revision's group",
+    );
+}
+
+
+//
+// Group Revision
+//
+#[test]
+fn group_revision_link_from_revision_author_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g1a = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), g1a.action_address().to_owned(), LinkTypes::GroupRevision, () );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::GroupRevision, &link ) ) );
+}
+
+#[test]
+fn group_revision_link_from_other_agent_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice, &bobby ], &[] ) );
+    let g1a = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice, &bobby ], &[] ) ) );
+    let link = dht.create_link( &bobby, g1.action_address().to_owned(), g1a.action_address().to_owned(), LinkTypes::GroupRevision, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupRevision, &link ) ),
+        "can only be made by the author of the revision",
+    );
+}
+
+#[test]
+fn group_revision_link_from_other_group_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g2 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g1a = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let link = dht.create_link( &alice, g2.action_address().to_owned(), g1a.action_address().to_owned(), LinkTypes::GroupRevision, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupRevision, &link ) ),
+        "is not a revision of the base group",
+    );
+}
+
+
+//
+// Group Resignation
+//
+#[test]
+fn group_resignation_by_member_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let link = dht.create_link( &bobby, g1.action_address().to_owned(), bobby.to_owned(), LinkTypes::GroupResignation, () );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::GroupResignation, &link ) ) );
+}
+
+#[test]
+fn group_resignation_for_other_agent_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby, &carol ] ) );
+    let link = dht.create_link( &carol, g1.action_address().to_owned(), bobby.to_owned(), LinkTypes::GroupResignation, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupResignation, &link ) ),
+        "can only be made by the resigning agent",
+    );
+}
+
+#[test]
+fn group_resignation_by_admin_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), alice.to_owned(), LinkTypes::GroupResignation, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupResignation, &link ) ),
+        "Only members of the base group revision can resign",
+    );
+}
+
+
+//
+// Key Succession
+//
+#[test]
+fn key_succession_link_from_predecessor_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let bobby_v2 = agent(12);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let succession = dht.key_succession( &g1, &bobby, &bobby_v2, None );
+    let link = dht.create_link( &bobby, anchor, succession.action_address().to_owned(), LinkTypes::KeySuccession, () );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::KeySuccession, &link ) ) );
+}
+
+#[test]
+fn key_succession_link_to_other_agents_succession_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let bobby_v2 = agent(12);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let succession = dht.key_succession( &g1, &bobby, &bobby_v2, None );
+    let link = dht.create_link( &alice, anchor, succession.action_address().to_owned(), LinkTypes::KeySuccession, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::KeySuccession, &link ) ),
+        "must be a succession from the anchor agent",
+    );
+}
+
+
+//
+// Contribution
+//
+#[test]
+fn contribution_link_from_own_anchor_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ) );
+}
+
+#[test]
+fn contribution_link_from_other_agents_anchor_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ),
+        "link based on an auth anchor can only be made by the matching agent",
+    );
+}
+
+#[test]
+fn contribution_link_after_resignation_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    dht.create_link( &bobby, g1.action_address().to_owned(), bobby.to_owned(), LinkTypes::GroupResignation, () );
+    // An admin keeping the member in a later revision does not undo the resignation
+    dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[ &bobby ] ) ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ),
+        "cannot be done after the agent resigned from the group",
+    );
+}
+
+#[test]
+fn contribution_link_after_resignation_from_other_group_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let g2 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    dht.create_link( &bobby, g2.action_address().to_owned(), bobby.to_owned(), LinkTypes::GroupResignation, () );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ) );
+}
+
+#[test]
+fn contribution_link_before_membership_window_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let mut group = group_entry( &[ &alice ], &[ &bobby ] );
+    group.membership_windows.push( MembershipWindow {
+        agent: bobby.to_owned(),
+        valid_from: Some( Timestamp::from_micros( i64::MAX ) ),
+        valid_until: None,
+    });
+    let g1 = dht.create_group( &alice, &group );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ),
+        "must be within the agent's membership window",
+    );
+}
+
+#[test]
+fn contribution_update_link_after_membership_window_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let mut group = group_entry( &[ &alice ], &[ &bobby ] );
+    group.membership_windows.push( MembershipWindow {
+        agent: bobby.to_owned(),
+        valid_from: None,
+        valid_until: Some( Timestamp::from_micros( 0 ) ),
+    });
+    let g1 = dht.create_group( &alice, &group );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let update = dht.update_content( &bobby, &content );
+    let link = dht.create_link( &bobby, anchor, update.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( update_tag( &content, &content ), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ),
+        "must be within the agent's membership window",
+    );
+}
+
+#[test]
+fn contribution_link_within_membership_window_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let mut group = group_entry( &[ &alice ], &[ &bobby ] );
+    group.membership_windows.push( MembershipWindow {
+        agent: bobby.to_owned(),
+        valid_from: Some( Timestamp::from_micros( 0 ) ),
+        valid_until: Some( Timestamp::from_micros( i64::MAX ) ),
+    });
+    let g1 = dht.create_group( &alice, &group );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ) );
+}
+
+#[test]
+fn contribution_link_outside_window_of_governing_revision_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let mut group = group_entry( &[ &alice ], &[ &bobby ] );
+    group.membership_windows.push( MembershipWindow {
+        agent: bobby.to_owned(),
+        valid_from: None,
+        valid_until: Some( Timestamp::from_micros( 0 ) ),
+    });
+    let g2 = dht.update_entry( &alice, &g1, &EntryTypes::Group( group ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g2.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ),
+        "must be within the agent's membership window in the governing group revision",
+    );
+}
+
+#[test]
+fn contribution_link_without_governing_revision_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::Contribution, () );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ),
+        "must end with a 39 byte governing group revision",
+    );
+}
+
+#[test]
+fn contribution_link_governed_by_other_group_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g2 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g2.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ),
+        "is not a revision of the anchor's group",
+    );
+}
+
+#[test]
+fn contribution_link_after_resignation_and_readding_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    dht.create_link( &bobby, g1.action_address().to_owned(), bobby.to_owned(), LinkTypes::GroupResignation, () );
+    let g2 = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let g3 = dht.update_entry( &alice, &g2, &EntryTypes::Group( group_entry( &[ &alice ], &[ &bobby ] ) ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g3.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ) );
+}
+
+#[test]
+fn contribution_link_from_archive_anchor_by_admin_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ) );
+}
+
+#[test]
+fn contribution_link_from_archive_anchor_by_non_admin_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ),
+        "auth archive anchor can only be made by group admins",
+    );
+}
+
+#[test]
+fn contribution_link_with_typed_tag_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let tag = ContributionTag {
+        collection: Some("docs".to_string()),
+        kind: "post".to_string(),
+        label: Some("Hello world".to_string()),
+        created_at: Some( 1_700_000_000_000 ),
+    };
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( tag.to_bytes().unwrap(), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ) );
+}
+
+#[test]
+fn contribution_link_with_unsupported_tag_version_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let mut tag_bytes = ContributionTag::kind_prefix( "", "post" );
+    tag_bytes[0] = 2;
+    tag_bytes.push( 0 ); // empty label
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( tag_bytes, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ),
+        "Unsupported contribution tag version",
+    );
+}
+
+#[test]
+fn contribution_link_with_malformed_tag_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let mut tag_bytes = ContributionTag::kind_prefix( "docs", "post" );
+    tag_bytes.push( 3 ); // label length without the label bytes
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( tag_bytes, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::Contribution, &link ) ),
+        "Contribution link has malformed tag",
+    );
+}
+
+
+//
+// Contribution Update
+//
+fn update_tag(id: &Record, rev: &Record) -> Vec<u8> {
+    ContributionUpdateTag {
+        content_id: id.action_address().to_owned().into(),
+        content_rev: rev.action_address().to_owned().into(),
+    }.to_bytes()
+}
+
+fn legacy_update_tag(id: &Record, rev: &Record) -> Vec<u8> {
+    format!("{}:{}", id.action_address(), rev.action_address() ).into_bytes()
+}
+
+#[test]
+fn contribution_update_link_with_matching_tag_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let c1a = dht.update_content( &alice, &c1 );
+    let c1b = dht.update_content( &alice, &c1a );
+    let link = dht.create_link( &alice, anchor, c1b.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( update_tag( &c1, &c1a ), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ) );
+}
+
+#[test]
+fn contribution_update_link_with_legacy_tag_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let c1a = dht.update_content( &alice, &c1 );
+    let link = dht.create_link( &alice, anchor, c1a.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( legacy_update_tag( &c1, &c1 ), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ) );
+}
+
+#[test]
+fn contribution_update_link_with_truncated_tag_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let c1a = dht.update_content( &alice, &c1 );
+    let mut tag = update_tag( &c1, &c1 );
+    tag.truncate( 40 );
+    let link = dht.create_link( &alice, anchor, c1a.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( tag, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ),
+        "Update tag must be 79 bytes",
+    );
+}
+
+#[test]
+fn contribution_update_link_from_other_agents_anchor_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let c1a = dht.update_content( &bobby, &c1 );
+    let link = dht.create_link( &bobby, anchor, c1a.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( update_tag( &c1, &c1 ), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ),
+        "link based on an auth anchor can only be made by the matching agent",
+    );
+}
+
+#[test]
+fn contribution_update_link_with_non_utf8_tag_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let c1a = dht.update_content( &alice, &c1 );
+    let link = dht.create_link( &alice, anchor, c1a.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( vec![ 0xff, 0xfe ], g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ),
+        "tag must be a UTF8 string",
+    );
+}
+
+#[test]
+fn contribution_update_link_without_separator_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let c1a = dht.update_content( &alice, &c1 );
+    let tag = c1.action_address().to_string().into_bytes();
+    let link = dht.create_link( &alice, anchor, c1a.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( tag, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ),
+        "has malformed tag",
+    );
+}
+
+#[test]
+fn contribution_update_link_with_invalid_hash_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let c1a = dht.update_content( &alice, &c1 );
+    let tag = format!("not-a-hash:{}", c1.action_address() ).into_bytes();
+    let link = dht.create_link( &alice, anchor, c1a.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( tag, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ),
+        "Invalid tag part 1",
+    );
+}
+
+#[test]
+fn contribution_update_link_with_unrelated_tag_parts_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let c2 = dht.create_content( &alice );
+    let c2a = dht.update_content( &alice, &c2 );
+    let link = dht.create_link( &alice, anchor, c2a.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( update_tag( &c1, &c2 ), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ),
+        "not the root of the tag revision",
+    );
+}
+
+
+#[test]
+fn contribution_update_link_to_unrelated_update_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let c2 = dht.create_content( &alice );
+    let c2a = dht.update_content( &alice, &c2 );
+    let link = dht.create_link( &alice, anchor, c2a.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( update_tag( &c1, &c1 ), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ),
+        "is not an update of the tag revision",
+    );
+}
+
+#[test]
+fn contribution_update_link_to_tag_revision_itself_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let c1 = dht.create_content( &alice );
+    let link = dht.create_link( &alice, anchor, c1.action_address().to_owned(), LinkTypes::ContributionUpdate, governed( update_tag( &c1, &c1 ), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionUpdate, &link ) ),
+        "is not an update of the tag revision",
+    );
+}
+
+
+//
+// Contribution Index
+//
+#[test]
+fn contribution_index_link_reversing_own_contribution_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let contribution = dht.create_link( &bobby, anchor.to_owned(), content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    let tag = contribution.action_address().get_raw_39().to_vec();
+    let link = dht.create_link( &bobby, content.action_address().to_owned(), anchor, LinkTypes::ContributionIndex, tag );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::ContributionIndex, &link ) ) );
+}
+
+#[test]
+fn contribution_index_link_for_other_content_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let c1 = dht.create_content( &bobby );
+    let c2 = dht.create_content( &bobby );
+    let contribution = dht.create_link( &bobby, anchor.to_owned(), c1.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    let tag = contribution.action_address().get_raw_39().to_vec();
+    let link = dht.create_link( &bobby, c2.action_address().to_owned(), anchor, LinkTypes::ContributionIndex, tag );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionIndex, &link ) ),
+        "must reverse a contribution link by the same author",
+    );
+}
+
+#[test]
+fn contribution_index_link_to_other_agents_anchor_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let contribution = dht.create_link( &bobby, anchor.to_owned(), content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    let tag = contribution.action_address().get_raw_39().to_vec();
+    let link = dht.create_link( &alice, content.action_address().to_owned(), anchor, LinkTypes::ContributionIndex, tag );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContributionIndex, &link ) ),
+        "must be the author's contributions anchor",
+    );
+}
+
+
+//
+// Group Directory
+//
+fn directory_tag(group_rev: &ActionHash, name: &str, tags: &[&str]) -> Vec<u8> {
+    GroupDirectoryTag {
+        group_rev: group_rev.to_owned(),
+        name: name.to_string(),
+        tags: tags.iter().map(|tag| tag.to_string() ).collect(),
+    }.to_bytes().unwrap()
+}
+
+#[test]
+fn group_directory_link_from_admin_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    dht.install();
+    let tag = directory_tag( g1.action_address(), "Recipes", &[ "cooking" ] );
+    let name_anchor = group_directory_name_anchor( "Recipes" ).unwrap();
+    let tag_anchor = group_directory_tag_anchor( "cooking" ).unwrap();
+    let name_link = dht.create_link( &alice, name_anchor, g1.action_address().to_owned(), LinkTypes::GroupDirectory, tag.to_owned() );
+    let tag_link = dht.create_link( &alice, tag_anchor, g1.action_address().to_owned(), LinkTypes::GroupDirectory, tag );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::GroupDirectory, &name_link ) ) );
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::GroupDirectory, &tag_link ) ) );
+}
+
+#[test]
+fn group_directory_link_from_member_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    dht.install();
+    let tag = directory_tag( g1.action_address(), "Recipes", &[] );
+    let anchor = group_directory_name_anchor( "Recipes" ).unwrap();
+    let link = dht.create_link( &bobby, anchor, g1.action_address().to_owned(), LinkTypes::GroupDirectory, tag );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupDirectory, &link ) ),
+        "Only admins of the group can list it",
+    );
+}
+
+#[test]
+fn group_directory_link_from_unlisted_anchor_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    dht.install();
+    let tag = directory_tag( g1.action_address(), "Recipes", &[ "cooking" ] );
+    let anchor = group_directory_tag_anchor( "gardening" ).unwrap();
+    let link = dht.create_link( &alice, anchor, g1.action_address().to_owned(), LinkTypes::GroupDirectory, tag );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupDirectory, &link ) ),
+        "base must be the name, tag or group anchor of the listing",
+    );
+}
+
+#[test]
+fn group_directory_link_from_group_anchor_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    dht.install();
+    let tag = directory_tag( g1.action_address(), "Recipes", &[] );
+    let anchor = group_directory_group_anchor( g1.action_address() ).unwrap();
+    let link = dht.create_link( &alice, anchor, g1.action_address().to_owned(), LinkTypes::GroupDirectory, tag );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::GroupDirectory, &link ) ) );
+}
+
+#[test]
+fn group_directory_link_from_other_group_anchor_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g2 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    dht.install();
+    let tag = directory_tag( g1.action_address(), "Recipes", &[] );
+    let anchor = group_directory_group_anchor( g2.action_address() ).unwrap();
+    let link = dht.create_link( &alice, anchor, g1.action_address().to_owned(), LinkTypes::GroupDirectory, tag );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupDirectory, &link ) ),
+        "base must be the name, tag or group anchor of the listing",
+    );
+}
+
+#[test]
+fn group_directory_link_with_name_shorter_than_shard_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    dht.install();
+    // Encoded by hand because the tag encoder rejects short names
+    let mut tag = vec![ GROUP_DIRECTORY_TAG_VERSION ];
+    tag.extend_from_slice( g1.action_address().get_raw_39() );
+    tag.extend_from_slice( &[ 1, b'R', 0 ] );
+    let anchor = group_directory_name_anchor( "R" ).unwrap();
+    let link = dht.create_link( &alice, anchor, g1.action_address().to_owned(), LinkTypes::GroupDirectory, tag );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupDirectory, &link ) ),
+        "name must be at least 2 characters",
+    );
+}
+
+#[test]
+fn group_directory_link_to_other_group_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g2 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    dht.install();
+    let tag = directory_tag( g1.action_address(), "Recipes", &[] );
+    let anchor = group_directory_name_anchor( "Recipes" ).unwrap();
+    let link = dht.create_link( &alice, anchor, g2.action_address().to_owned(), LinkTypes::GroupDirectory, tag );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::GroupDirectory, &link ) ),
+        "is not a revision of the target group",
+    );
+}
+
+
+//
+// Encryption Key
+//
+#[test]
+fn encryption_key_link_from_own_agent_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let link = dht.create_link( &alice, alice.to_owned(), alice.to_owned(), LinkTypes::EncryptionKey, vec![ 7; 32 ] );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::EncryptionKey, &link ) ) );
+}
+
+#[test]
+fn encryption_key_link_for_other_agent_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let link = dht.create_link( &alice, bobby.to_owned(), bobby.to_owned(), LinkTypes::EncryptionKey, vec![ 7; 32 ] );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::EncryptionKey, &link ) ),
+        "from and to the author's agent pubkey",
+    );
+}
+
+#[test]
+fn encryption_key_link_with_malformed_key_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let link = dht.create_link( &alice, alice.to_owned(), alice.to_owned(), LinkTypes::EncryptionKey, vec![ 7; 16 ] );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::EncryptionKey, &link ) ),
+        "must be a 32 byte X25519 public key",
+    );
+}
+
+
+//
+// Content Proposal Resolution
+//
+fn resolution_tag(group_rev: &ActionHash, accepted: bool, reason: &str) -> Vec<u8> {
+    ContentProposalResolutionTag {
+        accepted,
+        group_rev: group_rev.to_owned(),
+        reason: reason.to_string(),
+    }.to_bytes().unwrap()
+}
+
+#[test]
+fn content_proposal_resolution_by_member_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let content = dht.create_content( &alice );
+    let proposed = dht.update_content( &carol, &content );
+    let proposal = dht.create_entry( &carol, &content_proposal_entry( &g1, &content, &proposed ) );
+    let tag = resolution_tag( g1.action_address(), false, "Not a typo" );
+    let link = dht.create_link( &bobby, proposal.action_address().to_owned(), bobby.to_owned(), LinkTypes::ContentProposalResolution, tag );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::ContentProposalResolution, &link ) ) );
+}
+
+#[test]
+fn content_proposal_resolution_by_non_member_is_invalid() {
+    let alice = agent(1);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let content = dht.create_content( &alice );
+    let proposed = dht.update_content( &carol, &content );
+    let proposal = dht.create_entry( &carol, &content_proposal_entry( &g1, &content, &proposed ) );
+    let tag = resolution_tag( g1.action_address(), true, "" );
+    let link = dht.create_link( &carol, proposal.action_address().to_owned(), carol.to_owned(), LinkTypes::ContentProposalResolution, tag );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentProposalResolution, &link ) ),
+        "Only contributors of the group can resolve",
+    );
+}
+
+#[test]
+fn content_proposal_rejection_without_reason_is_invalid() {
+    let alice = agent(1);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let content = dht.create_content( &alice );
+    let proposed = dht.update_content( &carol, &content );
+    let proposal = dht.create_entry( &carol, &content_proposal_entry( &g1, &content, &proposed ) );
+    let mut tag = resolution_tag( g1.action_address(), true, "" );
+    tag[1] = 0;
+    let link = dht.create_link( &alice, proposal.action_address().to_owned(), alice.to_owned(), LinkTypes::ContentProposalResolution, tag );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentProposalResolution, &link ) ),
+        "must have a reason",
+    );
+}
+
+
+//
+// Content Position
+//
+fn position_tag(key: &str) -> Vec<u8> {
+    ContentPositionTag {
+        collection: None,
+        key: key.to_string(),
+        original: None,
+    }.to_bytes().unwrap()
+}
+
+fn archived_position_tag(original: &Record) -> Vec<u8> {
+    let tag = match original.action() {
+        Action::CreateLink(action) => ContentPositionTag::from_bytes( &action.tag.0 ).unwrap(),
+        action => panic!("Expected a create link action; not {:?}", action ),
+    };
+
+    tag.copy_of( original.action_address().to_owned() ).to_bytes().unwrap()
+}
+
+#[test]
+fn content_position_link_from_own_anchor_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentPosition, governed( position_tag("V"), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::ContentPosition, &link ) ) );
+}
+
+#[test]
+fn content_position_link_from_other_agents_anchor_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::ContentPosition, governed( position_tag("V"), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentPosition, &link ) ),
+        "link based on an auth anchor can only be made by the matching agent",
+    );
+}
+
+#[test]
+fn content_position_link_from_archive_anchor_without_original_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentPosition, governed( position_tag("V"), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentPosition, &link ) ),
+        "must reference the original link",
+    );
+}
+
+#[test]
+fn archived_content_position_link_copy_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let active_anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let original = dht.create_link( &bobby, active_anchor, content.action_address().to_owned(), LinkTypes::ContentPosition, governed( position_tag("V"), g1.action_address() ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentPosition, governed( archived_position_tag( &original ), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::ContentPosition, &link ) ) );
+}
+
+#[test]
+fn archived_content_position_link_with_altered_key_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let active_anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let original = dht.create_link( &bobby, active_anchor, content.action_address().to_owned(), LinkTypes::ContentPosition, governed( position_tag("V"), g1.action_address() ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let tag = ContentPositionTag {
+        collection: None,
+        key: "1".to_string(),
+        original: Some( original.action_address().to_owned() ),
+    }.to_bytes().unwrap();
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentPosition, governed( tag, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentPosition, &link ) ),
+        "must match its original link",
+    );
+}
+
+#[test]
+fn content_position_link_from_own_anchor_with_original_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let original = dht.create_link( &alice, anchor.to_owned(), content.action_address().to_owned(), LinkTypes::ContentPosition, governed( position_tag("V"), g1.action_address() ) );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentPosition, governed( archived_position_tag( &original ), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentPosition, &link ) ),
+        "cannot reference an original link",
+    );
+}
+
+#[test]
+fn content_position_link_with_trailing_zero_key_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let mut tag_bytes = ContentPositionTag::collection_prefix("");
+    tag_bytes.extend_from_slice( &[ 2, b'V', b'0' ] );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentPosition, governed( tag_bytes, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentPosition, &link ) ),
+        "Position key cannot end with '0'",
+    );
+}
+
+
+//
+// Content Tombstone
+//
+fn tombstone_tag(deleted: bool, timestamp: Timestamp) -> Vec<u8> {
+    ContentTombstoneTag {
+        deleted,
+        timestamp,
+        original: None,
+    }.to_bytes()
+}
+
+fn archived_tombstone_tag(original: &Record) -> Vec<u8> {
+    let tag = match original.action() {
+        Action::CreateLink(action) => ContentTombstoneTag::from_bytes( &action.tag.0 ).unwrap(),
+        action => panic!("Expected a create link action; not {:?}", action ),
+    };
+
+    tag.copy_of( original.action_address().to_owned() ).to_bytes()
+}
+
+#[test]
+fn content_tombstone_link_with_link_timestamp_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let tag = tombstone_tag( true, dht.next_timestamp() );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( tag, g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::ContentTombstone, &link ) ) );
+}
+
+#[test]
+fn content_tombstone_link_with_future_timestamp_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let tag = tombstone_tag( true, Timestamp::from_micros( i64::MAX ) );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( tag, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentTombstone, &link ) ),
+        "cannot be after the link timestamp",
+    );
+}
+
+#[test]
+fn archived_content_tombstone_link_keeps_original_timestamp() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let active_anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let tag = tombstone_tag( false, dht.next_timestamp() );
+    let original = dht.create_link( &bobby, active_anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( tag, g1.action_address() ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( archived_tombstone_tag( &original ), g1.action_address() ) );
+    dht.install();
+
+    assert_valid( validate_flat_op( create_link_op( LinkTypes::ContentTombstone, &link ) ) );
+}
+
+#[test]
+fn archived_content_tombstone_link_without_original_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let tag = tombstone_tag( false, Timestamp::from_micros( 1 ) );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( tag, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentTombstone, &link ) ),
+        "must reference the original link",
+    );
+}
+
+#[test]
+fn archived_content_tombstone_link_with_altered_timestamp_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let active_anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let tag = tombstone_tag( true, dht.next_timestamp() );
+    let original = dht.create_link( &bobby, active_anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( tag, g1.action_address() ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let tag = ContentTombstoneTag {
+        deleted: true,
+        timestamp: Timestamp::from_micros( i64::MAX ),
+        original: Some( original.action_address().to_owned() ),
+    }.to_bytes();
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( tag, g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentTombstone, &link ) ),
+        "must match its original link",
+    );
+}
+
+#[test]
+fn archived_content_tombstone_link_from_other_agent_original_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby, &carol ] ) );
+    let active_anchor = dht.anchor( g1.action_address(), &carol );
+    let content = dht.create_content( &carol );
+    let tag = tombstone_tag( true, dht.next_timestamp() );
+    let original = dht.create_link( &carol, active_anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( tag, g1.action_address() ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( archived_tombstone_tag( &original ), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentTombstone, &link ) ),
+        "must be based on the anchor agent's auth anchor",
+    );
+}
+
+#[test]
+fn content_tombstone_link_from_auth_anchor_with_original_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let tag = tombstone_tag( true, dht.next_timestamp() );
+    let original = dht.create_link( &alice, anchor.to_owned(), content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( tag, g1.action_address() ) );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( archived_tombstone_tag( &original ), g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentTombstone, &link ) ),
+        "cannot reference an original link",
+    );
+}
+
+#[test]
+fn content_tombstone_link_with_malformed_tag_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( vec![ 1, 2 ], g1.action_address() ) );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( create_link_op( LinkTypes::ContentTombstone, &link ) ),
+        "Content tombstone link has malformed tag",
+    );
+}
@@ -0,0 +1,79 @@
+use crate::{
+    EntryTypes,
+    ContributionsAnchorEntry,
+    ArchivedContributionsAnchorEntry,
+};
+use super::super::validate_flat_op;
+use super::fixtures::*;
+
+
+#[test]
+fn group_delete_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let record = dht.delete_entry( &alice, &g1 );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_entry_op( &record ) ),
+        "Groups cannot be deleted",
+    );
+}
+
+#[test]
+fn contributions_anchor_delete_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.create_entry(
+        &alice,
+        &EntryTypes::ContributionsAnchor(
+            ContributionsAnchorEntry( g1.action_address().to_owned(), alice.to_owned() )
+        )
+    );
+    let record = dht.delete_entry( &alice, &anchor );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_entry_op( &record ) ),
+        "Anchors are required",
+    );
+}
+
+#[test]
+fn archived_contributions_anchor_delete_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.create_entry(
+        &alice,
+        &EntryTypes::ArchivedContributionsAnchor(
+            ArchivedContributionsAnchorEntry::new( g1.action_address().to_owned(), alice.to_owned() )
+        )
+    );
+    let record = dht.delete_entry( &alice, &anchor );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_entry_op( &record ) ),
+        "Anchors are required",
+    );
+}
+
+#[test]
+fn key_succession_delete_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let bobby_v2 = agent(12);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let succession = dht.key_succession( &g1, &bobby, &bobby_v2, None );
+    let record = dht.delete_entry( &bobby, &succession );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_entry_op( &record ) ),
+        "Key successions are required",
+    );
+}
@@ -0,0 +1,266 @@
+use crate::{
+    EntryTypes,
+    LinkTypes,
+    ContentTombstoneTag,
+    GroupDirectoryTag,
+    group_directory_name_anchor,
+};
+use super::super::validate_flat_op;
+use super::fixtures::*;
+
+
+#[test]
+fn group_auth_link_delete_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), anchor, LinkTypes::GroupAuth, () );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "group auth links cannot be deleted",
+    );
+}
+
+#[test]
+fn group_auth_archive_link_delete_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), anchor, LinkTypes::GroupAuthArchive, () );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "group auth links cannot be deleted",
+    );
+}
+
+#[test]
+fn group_merge_link_delete_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g1a = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let g1b = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let merge = dht.update_entry( &alice, &g1a, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let link = dht.create_link( &alice, g1b.action_address().to_owned(), merge.action_address().to_owned(), LinkTypes::GroupMerge, () );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "group merge links cannot be deleted",
+    );
+}
+
+#[test]
+fn group_revision_link_delete_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let g1a = dht.update_entry( &alice, &g1, &EntryTypes::Group( group_entry( &[ &alice ], &[] ) ) );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), g1a.action_address().to_owned(), LinkTypes::GroupRevision, () );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "group revision links cannot be deleted",
+    );
+}
+
+#[test]
+fn group_resignation_link_delete_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let link = dht.create_link( &bobby, g1.action_address().to_owned(), bobby.to_owned(), LinkTypes::GroupResignation, () );
+    let delete = dht.delete_link( &bobby, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "group resignation links cannot be deleted",
+    );
+}
+
+#[test]
+fn group_link_delete_by_author_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let link = dht.create_link( &alice, alice.to_owned(), g1.action_address().to_owned(), LinkTypes::Group, () );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_valid( validate_flat_op( delete_link_op( &delete ) ) );
+}
+
+#[test]
+fn group_link_delete_by_other_agent_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let link = dht.create_link( &alice, alice.to_owned(), g1.action_address().to_owned(), LinkTypes::Group, () );
+    let delete = dht.delete_link( &bobby, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "can only be deleted by the author who created it",
+    );
+}
+
+#[test]
+fn contribution_link_delete_by_anchor_agent_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_valid( validate_flat_op( delete_link_op( &delete ) ) );
+}
+
+#[test]
+fn contribution_link_delete_by_other_agent_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let anchor = dht.anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &bobby, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "can only be deleted by the matching anchor agent",
+    );
+}
+
+#[test]
+fn archived_contribution_link_delete_by_contributor_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_valid( validate_flat_op( delete_link_op( &delete ) ) );
+}
+
+#[test]
+fn archived_contribution_link_delete_by_non_contributor_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.archive_anchor( g1.action_address(), &bobby );
+    let content = dht.create_content( &bobby );
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::Contribution, governed( (), g1.action_address() ) );
+    let delete = dht.delete_link( &bobby, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "can only be deleted by an admin",
+    );
+}
+
+#[test]
+fn content_tombstone_link_delete_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = dht.anchor( g1.action_address(), &alice );
+    let content = dht.create_content( &alice );
+    let tag = ContentTombstoneTag {
+        deleted: true,
+        timestamp: dht.next_timestamp(),
+        original: None,
+    }.to_bytes();
+    let link = dht.create_link( &alice, anchor, content.action_address().to_owned(), LinkTypes::ContentTombstone, governed( tag, g1.action_address() ) );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "content tombstone links cannot be deleted",
+    );
+}
+
+#[test]
+fn group_directory_link_delete_by_admin_is_valid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    dht.install();
+    let tag = GroupDirectoryTag {
+        group_rev: g1.action_address().to_owned(),
+        name: "Recipes".to_string(),
+        tags: vec![],
+    }.to_bytes().unwrap();
+    let anchor = group_directory_name_anchor( "Recipes" ).unwrap();
+    let link = dht.create_link( &alice, anchor, g1.action_address().to_owned(), LinkTypes::GroupDirectory, tag );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_valid( validate_flat_op( delete_link_op( &delete ) ) );
+}
+
+#[test]
+fn group_directory_link_delete_by_member_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    dht.install();
+    let tag = GroupDirectoryTag {
+        group_rev: g1.action_address().to_owned(),
+        name: "Recipes".to_string(),
+        tags: vec![],
+    }.to_bytes().unwrap();
+    let anchor = group_directory_name_anchor( "Recipes" ).unwrap();
+    let link = dht.create_link( &alice, anchor, g1.action_address().to_owned(), LinkTypes::GroupDirectory, tag );
+    let delete = dht.delete_link( &bobby, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "can only be deleted by an admin of the listed group revision",
+    );
+}
+
+#[test]
+fn group_key_link_delete_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let group_key = dht.create_entry( &alice, &group_key_entry( g1.action_address(), g1.action_address(), 0, &[ &alice ], None ) );
+    let link = dht.create_link( &alice, g1.action_address().to_owned(), group_key.action_address().to_owned(), LinkTypes::GroupKey, () );
+    let delete = dht.delete_link( &alice, &link );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( delete_link_op( &delete ) ),
+        "group key links cannot be deleted",
+    );
+}
@@ -0,0 +1,625 @@
+//! A fixture DHT for exercising validation without a conductor
+//!
+//! Records committed to a [`FixtureDht`] are served through a mocked HDI so that `must_get_*`
+//! calls (and the `hdi_extensions` helpers built on them, such as `summon_app_entry`) resolve
+//! against the fixtures.  Call [`FixtureDht::install`] after committing the records that a test
+//! depends on.
+use std::collections::{
+    BTreeMap, HashMap,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{
+    Hash, Hasher,
+};
+use crate::{
+    hdi,
+    EntryTypes,
+    EntryTypesUnit,
+    LinkTypes,
+    GroupEntry,
+    ContributionsAnchorEntry,
+    ArchivedContributionsAnchorEntry,
+    KeySuccessionEntry,
+    GroupKeyEntry,
+    GroupKeyShare,
+    ContentProposalEntry,
+    with_governing_revision,
+};
+use hdi::prelude::*;
+use hdi::hdi::{
+    set_hdi,
+    HdiT,
+};
+
+
+/// The zome index given to the `coop_content` integrity zome in fixtures
+pub const COOP_ZOME_INDEX : u8 = 0;
+
+/// The zome index given to foreign (content) entries in fixtures
+pub const CONTENT_ZOME_INDEX : u8 = 1;
+
+
+/// Get a deterministic agent pubkey for the given seed
+pub fn agent(seed: u8) -> AgentPubKey {
+    AgentPubKey::from_raw_36( vec![ seed; 36 ] )
+}
+
+/// Create a group entry with the given admins and members
+pub fn group_entry(admins: &[&AgentPubKey], members: &[&AgentPubKey]) -> GroupEntry {
+    GroupEntry {
+        admins: admins.iter().map(|pubkey| (*pubkey).to_owned() ).collect(),
+        members: members.iter().map(|pubkey| (*pubkey).to_owned() ).collect(),
+        deleted: None,
+        membership_windows: vec![],
+        content_type_restrictions: vec![],
+        published_at: 0,
+        last_updated: 0,
+        metadata: BTreeMap::new(),
+    }
+}
+
+pub fn key_succession_entry(
+    group: &Record,
+    predecessor: &AgentPubKey,
+    successor: &AgentPubKey,
+    previous: Option<ActionHash>,
+) -> EntryTypes {
+    EntryTypes::KeySuccession( KeySuccessionEntry {
+        group_id: group.action_address().to_owned(),
+        group_rev: group.action_address().to_owned(),
+        predecessor: predecessor.to_owned(),
+        successor: successor.to_owned(),
+        previous,
+    })
+}
+
+/// Create a group key entry with placeholder key material shared with the given agents
+pub fn group_key_entry(
+    group_id: &ActionHash,
+    group_rev: &ActionHash,
+    epoch: u32,
+    agents: &[&AgentPubKey],
+    previous: Option<ActionHash>,
+) -> EntryTypes {
+    EntryTypes::GroupKey( GroupKeyEntry {
+        group_id: group_id.to_owned(),
+        group_rev: group_rev.to_owned(),
+        epoch,
+        sender_key: vec![ 0; 32 ],
+        shares: agents.iter().map(|pubkey| GroupKeyShare {
+            agent: (*pubkey).to_owned(),
+            recipient_key: vec![ 0; 32 ],
+            nonce: vec![ 0; 24 ],
+            encrypted_key: vec![ 0; 48 ],
+        }).collect(),
+        previous,
+    })
+}
+
+/// Create a content proposal entry suggesting `proposed` as the next revision of `content`
+pub fn content_proposal_entry(group: &Record, content: &Record, proposed: &Record) -> EntryTypes {
+    EntryTypes::ContentProposal( ContentProposalEntry {
+        group_id: group.action_address().to_owned(),
+        content_id: content.action_address().to_owned().into(),
+        content_rev: content.action_address().to_owned().into(),
+        proposed_rev: proposed.action_address().to_owned().into(),
+    })
+}
+
+/// Append the governing group revision to a contributions anchor link tag
+pub fn governed(tag: impl Into<LinkTag>, group_rev: &ActionHash) -> Vec<u8> {
+    with_governing_revision( tag.into().into_inner(), group_rev )
+}
+
+
+
+/// A set of records and entries served to validation through a mocked HDI
+///
+/// Each agent's actions are chained through `prev_action` so that their chain can be served as
+/// agent activity.
+#[derive(Default)]
+pub struct FixtureDht {
+    counter: u32,
+    records: HashMap<ActionHash, Record>,
+    entries: HashMap<EntryHash, Entry>,
+    chain_heads: HashMap<AgentPubKey, ActionHash>,
+}
+
+impl FixtureDht {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn next_bytes(&mut self) -> Vec<u8> {
+        self.counter += 1;
+
+        let mut bytes = vec![ 0; 32 ];
+        bytes[..4].copy_from_slice( &self.counter.to_le_bytes() );
+        with_location( bytes )
+    }
+
+    /// The previous action for the author's next action (a placeholder for their first action)
+    fn prev_action(&mut self, author: &AgentPubKey) -> ActionHash {
+        let placeholder = ActionHash::from_raw_36( self.next_bytes() );
+
+        self.chain_heads.get( author ).cloned().unwrap_or( placeholder )
+    }
+
+    /// The timestamp that the next committed action will have
+    pub fn next_timestamp(&self) -> Timestamp {
+        Timestamp::from_micros( self.counter as i64 * 1_000 )
+    }
+
+    fn commit(&mut self, action: Action, entry: Option<Entry>) -> Record {
+        let action_hash = ActionHash::from_raw_36( self.next_bytes() );
+        let signed_action = SignedActionHashed::with_presigned(
+            ActionHashed::with_pre_hashed( action, action_hash.to_owned() ),
+            Signature([ 0; 64 ]),
+        );
+        let record = Record::new( signed_action, entry );
+
+        self.chain_heads.insert( record.action().author().to_owned(), action_hash.to_owned() );
+        self.records.insert( action_hash, record.to_owned() );
+
+        record
+    }
+
+    fn store_entry(&mut self, entry: Entry) -> EntryHash {
+        let entry_hash = EntryHash::from_raw_36( self.next_bytes() );
+
+        self.entries.insert( entry_hash.to_owned(), entry );
+
+        entry_hash
+    }
+
+    /// Store an entry without an action (eg. an anchor that is only referenced by hash)
+    pub fn entry(&mut self, app_entry: &EntryTypes) -> EntryHash {
+        let (_, entry) = app_entry_parts( app_entry );
+
+        self.store_entry( entry )
+    }
+
+    /// Commit a create action for a `coop_content` entry
+    pub fn create_entry(&mut self, author: &AgentPubKey, app_entry: &EntryTypes) -> Record {
+        let (entry_index, entry) = app_entry_parts( app_entry );
+        let entry_hash = self.store_entry( entry.to_owned() );
+        let create = Create {
+            author: author.to_owned(),
+            timestamp: self.next_timestamp(),
+            action_seq: self.counter,
+            prev_action: self.prev_action( author ),
+            entry_type: EntryType::App(AppEntryDef::new(
+                entry_index,
+                COOP_ZOME_INDEX.into(),
+                EntryVisibility::Public,
+            )),
+            entry_hash,
+            weight: EntryRateWeight::default(),
+        };
+
+        self.commit( Action::Create(create), Some(entry) )
+    }
+
+    /// Commit an update action for a `coop_content` entry
+    pub fn update_entry(
+        &mut self,
+        author: &AgentPubKey,
+        original: &Record,
+        app_entry: &EntryTypes,
+    ) -> Record {
+        let (entry_index, entry) = app_entry_parts( app_entry );
+        let entry_hash = self.store_entry( entry.to_owned() );
+        let update = Update {
+            author: author.to_owned(),
+            timestamp: self.next_timestamp(),
+            action_seq: self.counter,
+            prev_action: self.prev_action( author ),
+            original_action_address: original.action_address().to_owned(),
+            original_entry_address: original.action().entry_hash()
+                .expect("Original record must have an entry")
+                .to_owned(),
+            entry_type: EntryType::App(AppEntryDef::new(
+                entry_index,
+                COOP_ZOME_INDEX.into(),
+                EntryVisibility::Public,
+            )),
+            entry_hash,
+            weight: EntryRateWeight::default(),
+        };
+
+        self.commit( Action::Update(update), Some(entry) )
+    }
+
+    /// Commit a delete action for any record
+    pub fn delete_entry(&mut self, author: &AgentPubKey, original: &Record) -> Record {
+        let delete = Delete {
+            author: author.to_owned(),
+            timestamp: self.next_timestamp(),
+            action_seq: self.counter,
+            prev_action: self.prev_action( author ),
+            deletes_address: original.action_address().to_owned(),
+            deletes_entry_address: original.action().entry_hash()
+                .expect("Original record must have an entry")
+                .to_owned(),
+            weight: RateWeight::default(),
+        };
+
+        self.commit( Action::Delete(delete), None )
+    }
+
+    /// Commit a create action for content that belongs to another zome
+    pub fn create_content(&mut self, author: &AgentPubKey) -> Record {
+        let entry_hash = EntryHash::from_raw_36( self.next_bytes() );
+        let create = Create {
+            author: author.to_owned(),
+            timestamp: self.next_timestamp(),
+            action_seq: self.counter,
+            prev_action: self.prev_action( author ),
+            entry_type: EntryType::App(AppEntryDef::new(
+                0.into(),
+                CONTENT_ZOME_INDEX.into(),
+                EntryVisibility::Public,
+            )),
+            entry_hash,
+            weight: EntryRateWeight::default(),
+        };
+
+        self.commit( Action::Create(create), None )
+    }
+
+    /// Commit an update action for content that belongs to another zome
+    pub fn update_content(&mut self, author: &AgentPubKey, original: &Record) -> Record {
+        let entry_hash = EntryHash::from_raw_36( self.next_bytes() );
+        let update = Update {
+            author: author.to_owned(),
+            timestamp: self.next_timestamp(),
+            action_seq: self.counter,
+            prev_action: self.prev_action( author ),
+            original_action_address: original.action_address().to_owned(),
+            original_entry_address: original.action().entry_hash()
+                .expect("Original record must have an entry")
+                .to_owned(),
+            entry_type: EntryType::App(AppEntryDef::new(
+                0.into(),
+                CONTENT_ZOME_INDEX.into(),
+                EntryVisibility::Public,
+            )),
+            entry_hash,
+            weight: EntryRateWeight::default(),
+        };
+
+        self.commit( Action::Update(update), None )
+    }
+
+    /// Commit a create link action
+    pub fn create_link<B,T>(
+        &mut self,
+        author: &AgentPubKey,
+        base: B,
+        target: T,
+        link_type: LinkTypes,
+        tag: impl Into<LinkTag>,
+    ) -> Record
+    where
+        B: Into<AnyLinkableHash>,
+        T: Into<AnyLinkableHash>,
+    {
+        let create_link = CreateLink {
+            author: author.to_owned(),
+            timestamp: self.next_timestamp(),
+            action_seq: self.counter,
+            prev_action: self.prev_action( author ),
+            base_address: base.into(),
+            target_address: target.into(),
+            zome_index: COOP_ZOME_INDEX.into(),
+            link_type: LinkType( link_type as u8 ),
+            tag: tag.into(),
+            weight: RateWeight::default(),
+        };
+
+        self.commit( Action::CreateLink(create_link), None )
+    }
+
+    /// Commit a delete link action
+    pub fn delete_link(&mut self, author: &AgentPubKey, create_link: &Record) -> Record {
+        let base_address = match create_link.action() {
+            Action::CreateLink(action) => action.base_address.to_owned(),
+            action => panic!("Expected a create link action; not {:?}", action ),
+        };
+        let delete_link = DeleteLink {
+            author: author.to_owned(),
+            timestamp: self.next_timestamp(),
+            action_seq: self.counter,
+            prev_action: self.prev_action( author ),
+            base_address,
+            link_add_address: create_link.action_address().to_owned(),
+        };
+
+        self.commit( Action::DeleteLink(delete_link), None )
+    }
+
+    /// Create a group and its contributions anchors the same way `create_group` does
+    pub fn create_group(&mut self, author: &AgentPubKey, group: &GroupEntry) -> Record {
+        let record = self.create_entry( author, &EntryTypes::Group(group.to_owned()) );
+
+        for pubkey in group.contributors() {
+            self.create_entry(
+                author,
+                &EntryTypes::ContributionsAnchor(ContributionsAnchorEntry(
+                    record.action_address().to_owned(),
+                    pubkey,
+                ))
+            );
+        }
+
+        record
+    }
+
+    /// Store a contributions anchor entry and return its address
+    pub fn anchor(&mut self, group_id: &ActionHash, pubkey: &AgentPubKey) -> EntryHash {
+        self.entry( &EntryTypes::ContributionsAnchor(ContributionsAnchorEntry(
+            group_id.to_owned(),
+            pubkey.to_owned(),
+        )))
+    }
+
+    /// Store an archived contributions anchor entry and return its address
+    pub fn archive_anchor(&mut self, group_rev: &ActionHash, pubkey: &AgentPubKey) -> EntryHash {
+        self.entry( &EntryTypes::ArchivedContributionsAnchor(ArchivedContributionsAnchorEntry::new(
+            group_rev.to_owned(),
+            pubkey.to_owned(),
+        )))
+    }
+
+    /// Commit a key succession from `predecessor` to `successor` in the given group
+    pub fn key_succession(
+        &mut self,
+        group: &Record,
+        predecessor: &AgentPubKey,
+        successor: &AgentPubKey,
+        previous: Option<ActionHash>,
+    ) -> Record {
+        self.create_entry( predecessor, &key_succession_entry( group, predecessor, successor, previous ) )
+    }
+
+    /// Serve the current fixtures through a mocked HDI for this thread
+    pub fn install(&self) {
+        set_hdi( FixtureHdi {
+            records: self.records.to_owned(),
+            entries: self.entries.to_owned(),
+        });
+    }
+}
+
+
+/// An HDI that resolves `must_get_*` calls against a snapshot of a [`FixtureDht`]
+struct FixtureHdi {
+    records: HashMap<ActionHash, Record>,
+    entries: HashMap<EntryHash, Entry>,
+}
+
+impl HdiT for FixtureHdi {
+    fn verify_signature(&self, _: VerifySignature) -> ExternResult<bool> {
+        unsupported("verify_signature")
+    }
+
+    // Path anchors (eg. the group directory) are hashed in validation
+    fn hash(&self, input: HashInput) -> ExternResult<HashOutput> {
+        match input {
+            HashInput::Entry(entry) => Ok( HashOutput::Entry( EntryHash::from_raw_36( fixture_hash( &entry ) ) ) ),
+            _ => unsupported("hash"),
+        }
+    }
+
+    fn must_get_entry(&self, input: MustGetEntryInput) -> ExternResult<EntryHashed> {
+        self.entries.get( &input.0 )
+            .map(|entry| EntryHashed::with_pre_hashed( entry.to_owned(), input.0.to_owned() ) )
+            .ok_or(not_found( &input.0 ))
+    }
+
+    fn must_get_action(&self, input: MustGetActionInput) -> ExternResult<SignedActionHashed> {
+        self.records.get( &input.0 )
+            .map(|record| record.signed_action.to_owned() )
+            .ok_or(not_found( &input.0 ))
+    }
+
+    fn must_get_valid_record(&self, input: MustGetValidRecordInput) -> ExternResult<Record> {
+        self.records.get( &input.0 )
+            .cloned()
+            .ok_or(not_found( &input.0 ))
+    }
+
+    // Only the default filter (from the chain top back to the first fixture action) is supported
+    fn must_get_agent_activity(&self, input: MustGetAgentActivityInput) -> ExternResult<Vec<RegisterAgentActivity>> {
+        let mut activity = vec![];
+        let mut next = self.records.get( &input.chain_filter.chain_top );
+
+        while let Some(record) = next.filter(|record| record.action().author() == &input.author ) {
+            activity.push( RegisterAgentActivity {
+                action: record.signed_action.to_owned(),
+                cached_entry: None,
+            });
+            next = record.action().prev_action()
+                .and_then(|prev_action| self.records.get( prev_action ) );
+        }
+
+        Ok( activity )
+    }
+
+    fn dna_info(&self, _: ()) -> ExternResult<DnaInfo> {
+        unsupported("dna_info")
+    }
+
+    fn zome_info(&self, _: ()) -> ExternResult<ZomeInfo> {
+        Ok( zome_info() )
+    }
+
+    fn trace(&self, _: TraceMsg) -> ExternResult<()> {
+        Ok(())
+    }
+
+    fn x_salsa20_poly1305_decrypt(&self, _: XSalsa20Poly1305Decrypt) -> ExternResult<Option<XSalsa20Poly1305Data>> {
+        unsupported("x_salsa20_poly1305_decrypt")
+    }
+
+    fn x_25519_x_salsa20_poly1305_decrypt(&self, _: X25519XSalsa20Poly1305Decrypt) -> ExternResult<Option<XSalsa20Poly1305Data>> {
+        unsupported("x_25519_x_salsa20_poly1305_decrypt")
+    }
+
+    fn ed_25519_x_salsa20_poly1305_decrypt(&self, _: Ed25519XSalsa20Poly1305Decrypt) -> ExternResult<XSalsa20Poly1305Data> {
+        unsupported("ed_25519_x_salsa20_poly1305_decrypt")
+    }
+}
+
+
+/// A deterministic stand-in for an entry hash (fixtures do not need collision resistance)
+fn fixture_hash(entry: &Entry) -> Vec<u8> {
+    let mut bytes = vec![];
+
+    for seed in 0..4_u64 {
+        let mut hasher = DefaultHasher::new();
+        seed.hash( &mut hasher );
+        entry.hash( &mut hasher );
+        bytes.extend_from_slice( &hasher.finish().to_le_bytes() );
+    }
+
+    with_location( bytes )
+}
+
+/// Append the DHT location bytes so that fixture hashes survive a string round-trip
+fn with_location(mut core: Vec<u8>) -> Vec<u8> {
+    let mut location = holo_hash::encode::holo_dht_location_bytes( &core );
+    core.append( &mut location );
+    core
+}
+
+fn unsupported<T>(name: &str) -> ExternResult<T> {
+    Err(wasm_error!(WasmErrorInner::Guest(format!("Fixture HDI does not support '{}'", name ))))
+}
+
+fn not_found<T: std::fmt::Display>(addr: &T) -> WasmError {
+    wasm_error!(WasmErrorInner::Host(format!("Fixture DHT does not contain: {}", addr )))
+}
+
+fn app_entry_parts(app_entry: &EntryTypes) -> (EntryDefIndex, Entry) {
+    let (unit, entry) = match app_entry {
+        EntryTypes::Group(entry) =>
+            (EntryTypesUnit::Group, Entry::try_from( entry )),
+        EntryTypes::ContributionsAnchor(entry) =>
+            (EntryTypesUnit::ContributionsAnchor, Entry::try_from( entry )),
+        EntryTypes::ArchivedContributionsAnchor(entry) =>
+            (EntryTypesUnit::ArchivedContributionsAnchor, Entry::try_from( entry )),
+        EntryTypes::KeySuccession(entry) =>
+            (EntryTypesUnit::KeySuccession, Entry::try_from( entry )),
+        EntryTypes::GroupKey(entry) =>
+            (EntryTypesUnit::GroupKey, Entry::try_from( entry )),
+        EntryTypes::ContentProposal(entry) =>
+            (EntryTypesUnit::ContentProposal, Entry::try_from( entry )),
+    };
+
+    ( EntryDefIndex( unit as u8 ), entry.expect("Fixture entry must serialize") )
+}
+
+fn zome_info() -> ZomeInfo {
+    ZomeInfo {
+        name: "coop_content".into(),
+        id: COOP_ZOME_INDEX.into(),
+        properties: SerializedBytes::try_from(()).unwrap(),
+        entry_defs: EntryDefs(vec![]),
+        extern_fns: vec![],
+        zome_types: ScopedZomeTypesSet {
+            entries: ScopedZomeTypes(vec![(
+                COOP_ZOME_INDEX.into(),
+                (0..EntryTypes::unit_iter().count() as u8).map( EntryDefIndex ).collect(),
+            )]),
+            links: ScopedZomeTypes(vec![(
+                COOP_ZOME_INDEX.into(),
+                (0..LinkTypes::iter().count() as u8).map( LinkType ).collect(),
+            )]),
+        },
+    }
+}
+
+
+//
+// Op builders
+//
+pub type TestOp = FlatOp<EntryTypes, LinkTypes>;
+
+pub fn create_entry_op(app_entry: EntryTypes, record: &Record) -> TestOp {
+    match record.action() {
+        Action::Create(action) => FlatOp::StoreRecord(OpRecord::CreateEntry {
+            app_entry,
+            action: action.to_owned(),
+        }),
+        action => panic!("Expected a create action; not {:?}", action ),
+    }
+}
+
+pub fn update_entry_op(app_entry: EntryTypes, record: &Record) -> TestOp {
+    match record.action() {
+        Action::Update(action) => FlatOp::StoreRecord(OpRecord::UpdateEntry {
+            original_action_hash: action.original_action_address.to_owned(),
+            original_entry_hash: action.original_entry_address.to_owned(),
+            app_entry,
+            action: action.to_owned(),
+        }),
+        action => panic!("Expected an update action; not {:?}", action ),
+    }
+}
+
+pub fn delete_entry_op(record: &Record) -> TestOp {
+    match record.action() {
+        Action::Delete(action) => FlatOp::StoreRecord(OpRecord::DeleteEntry {
+            original_action_hash: action.deletes_address.to_owned(),
+            original_entry_hash: action.deletes_entry_address.to_owned(),
+            action: action.to_owned(),
+        }),
+        action => panic!("Expected a delete action; not {:?}", action ),
+    }
+}
+
+pub fn create_link_op(link_type: LinkTypes, record: &Record) -> TestOp {
+    match record.action() {
+        Action::CreateLink(action) => FlatOp::StoreRecord(OpRecord::CreateLink {
+            base_address: action.base_address.to_owned(),
+            target_address: action.target_address.to_owned(),
+            tag: action.tag.to_owned(),
+            link_type,
+            action: action.to_owned(),
+        }),
+        action => panic!("Expected a create link action; not {:?}", action ),
+    }
+}
+
+pub fn delete_link_op(record: &Record) -> TestOp {
+    match record.action() {
+        Action::DeleteLink(action) => FlatOp::StoreRecord(OpRecord::DeleteLink {
+            original_action_hash: action.link_add_address.to_owned(),
+            base_address: action.base_address.to_owned(),
+            action: action.to_owned(),
+        }),
+        action => panic!("Expected a delete link action; not {:?}", action ),
+    }
+}
+
+
+//
+// Assertions
+//
+pub fn assert_valid(result: ExternResult<ValidateCallbackResult>) {
+    match result {
+        Ok(ValidateCallbackResult::Valid) => (),
+        other => panic!("Expected a valid result; got {:?}", other ),
+    }
+}
+
+pub fn assert_invalid(result: ExternResult<ValidateCallbackResult>, expected: &str) {
+    match result {
+        Ok(ValidateCallbackResult::Invalid(message)) => assert!(
+            message.contains( expected ),
+            "Expected invalid message to contain '{}'; got '{}'", expected, message
+        ),
+        other => panic!("Expected an invalid result; got {:?}", other ),
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{
+    EntryTypes,
+    ContributionsAnchorEntry,
+};
+use super::super::validate_flat_op;
+use super::fixtures::*;
+
+
+#[test]
+fn group_update_by_admin_is_valid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let carol = agent(3);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let group = EntryTypes::Group( group_entry( &[ &alice ], &[ &bobby, &carol ] ) );
+    let record = dht.update_entry( &alice, &g1, &group );
+    dht.install();
+
+    assert_valid( validate_flat_op( update_entry_op( group, &record ) ) );
+}
+
+#[test]
+fn group_update_by_member_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let group = EntryTypes::Group( group_entry( &[ &alice ], &[] ) );
+    let record = dht.update_entry( &bobby, &g1, &group );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( update_entry_op( group, &record ) ),
+        "group can only be done by an admin",
+    );
+}
+
+#[test]
+fn group_update_changing_admins_is_invalid() {
+    let alice = agent(1);
+    let bobby = agent(2);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[ &bobby ] ) );
+    let group = EntryTypes::Group( group_entry( &[ &alice, &bobby ], &[] ) );
+    let record = dht.update_entry( &alice, &g1, &group );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( update_entry_op( group, &record ) ),
+        "requires counter-signing",
+    );
+}
+
+#[test]
+fn anchor_update_is_invalid() {
+    let alice = agent(1);
+    let mut dht = FixtureDht::new();
+    let g1 = dht.create_group( &alice, &group_entry( &[ &alice ], &[] ) );
+    let anchor = EntryTypes::ContributionsAnchor(
+        ContributionsAnchorEntry( g1.action_address().to_owned(), alice.to_owned() )
+    );
+    let a1 = dht.create_entry( &alice, &anchor );
+    let record = dht.update_entry( &alice, &a1, &anchor );
+    dht.install();
+
+    assert_invalid(
+        validate_flat_op( update_entry_op( anchor, &record ) ),
+        "Update validation not implemented",
+    );
+}
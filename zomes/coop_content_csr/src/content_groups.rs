@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    must_get,
+};
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    create_link_input,
+
+    // Entry Structs
+    ContributionsAnchorEntry,
+
+    // Output Structs
+    ContentGroup,
+};
+
+
+
+#[hdk_extern]
+pub fn get_content_groups(content_addr: AnyLinkableHash) -> ExternResult<Vec<ContentGroup>> {
+    debug!("Get content groups: {}", content_addr );
+    let links = get_links(
+        create_link_input(
+            &content_addr,
+            &LinkTypes::ContributionIndex,
+            &None::<()>,
+        )?
+    )?;
+    let mut contributions : HashMap<EntryHash, Vec<ActionHash>> = HashMap::new();
+    let mut groups = vec![];
+
+    for link in links {
+        let anchor_addr = match link.target.into_entry_hash() {
+            Some(addr) => addr,
+            None => {
+                debug!("WARNING: Should be unreachable because LinkTypes::ContributionIndex validation only allows anchor targets");
+                continue;
+            },
+        };
+        let tag_bytes = link.tag.into_inner();
+
+        if tag_bytes.len() != 39 {
+            debug!("Skipping contribution index link with malformed tag: {} bytes", tag_bytes.len() );
+            continue;
+        }
+
+        let contribution = match ActionHash::from_raw_39( tag_bytes ) {
+            Ok(addr) => addr,
+            Err(err) => {
+                debug!("Skipping contribution index link with malformed tag: {}", err );
+                continue;
+            },
+        };
+        let anchor : ContributionsAnchorEntry = match must_get( &anchor_addr ).and_then(|entry| entry.try_into() ) {
+            Ok(anchor) => anchor,
+            Err(err) => {
+                debug!("Skipping contribution index link with unresolvable anchor ({}): {:?}", anchor_addr, err );
+                continue;
+            },
+        };
+
+        // The index link may outlive its contribution link (eg. deleted by an admin)
+        if !contributions.contains_key( &anchor_addr ) {
+            let links = get_links(
+                create_link_input(
+                    &anchor_addr,
+                    &LinkTypes::Contribution,
+                    &None::<()>,
+                )?
+            )?;
+
+            contributions.insert(
+                anchor_addr.to_owned(),
+                links.into_iter().map(|link| link.create_link_hash ).collect(),
+            );
+        }
+
+        if !contributions[ &anchor_addr ].contains( &contribution ) {
+            debug!("Skipping contribution index link because the contribution ({}) was deleted", contribution );
+            continue;
+        }
+
+        groups.push( ContentGroup {
+            group_id: anchor.group().to_owned(),
+            contributor: anchor.author().to_owned(),
+            anchor: anchor_addr,
+            contribution,
+        });
+    }
+
+    Ok( groups )
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    must_get,
+    resolve_action_addr,
+};
+use crate::scoped_types::entry_traits::*;
+use coop_content_sdk::{
+    // Entry Structs
+    GroupEntry,
+    ContributionAnchors,
+
+    // Input Structs
+    GetGroupContentInput,
+
+    // Output Structs
+    ContentRevision,
+    ContentRevisionAuthority,
+    ContentRevisionSource,
+};
+use crate::group_forks::latest_group_revision;
+
+
+
+/// A contributions anchor of the latest group revision with its loaded links
+struct HistoryAnchor {
+    authority: ContentRevisionAuthority,
+    content_ids: Vec<AnyLinkableHash>,
+    update_links: Vec<Link>,
+}
+
+fn history_anchors(group_id: &ActionHash) -> ExternResult<Vec<HistoryAnchor>> {
+    let group_rev = latest_group_revision( group_id )?;
+    let anchor_addrs = GroupEntry::group_auth_anchor_hashes( &group_rev )?.into_iter()
+        .chain( GroupEntry::group_auth_archive_anchor_hashes( &group_rev )? );
+    let mut anchors = vec![];
+
+    for anchor_addr in anchor_addrs {
+        let anchor : ContributionAnchors = must_get( &anchor_addr )?.try_into()?;
+        let (content_ids, update_links, authorizing_rev) = match &anchor {
+            ContributionAnchors::Active(anchor) => ( anchor.create_targets()?, anchor.update_links()?, group_rev.to_owned() ),
+            ContributionAnchors::Archive(anchor) => ( anchor.create_targets()?, anchor.update_links()?, anchor.group().to_owned() ),
+        };
+
+        anchors.push( HistoryAnchor {
+            authority: ContentRevisionAuthority {
+                anchor: anchor_addr,
+                agent: anchor.author().to_owned(),
+                archived: anchor.is_archive(),
+                group_rev: authorizing_rev,
+            },
+            content_ids,
+            update_links,
+        });
+    }
+
+    Ok( anchors )
+}
+
+/// Get the anchors of the latest group revision that registered each content ID
+///
+/// A content ID can be registered by more than one anchor (eg. the archive copy and the original
+/// active anchor of a member that was removed and re-added).
+pub fn content_registrations(
+    group_id: &ActionHash,
+) -> ExternResult<HashMap<AnyLinkableHash, Vec<ContentRevisionAuthority>>> {
+    let mut registrations : HashMap<AnyLinkableHash, Vec<ContentRevisionAuthority>> = HashMap::new();
+
+    for anchor in history_anchors( group_id )? {
+        for content_id in anchor.content_ids {
+            registrations.entry( content_id )
+                .or_default()
+                .push( anchor.authority.to_owned() );
+        }
+    }
+
+    Ok( registrations )
+}
+
+fn find_authority(
+    anchors: &[HistoryAnchor],
+    address: &AnyLinkableHash,
+    author: &AgentPubKey,
+    source: &ContentRevisionSource,
+) -> Option<ContentRevisionAuthority> {
+    let has_update = |anchor: &&HistoryAnchor| anchor.update_links.iter()
+        .any(|link| &link.target == address );
+
+    let anchor = match source {
+        ContentRevisionSource::Contribution => anchors.iter()
+            .find(|anchor| anchor.content_ids.contains( address ) ),
+        ContentRevisionSource::Shortcut => anchors.iter()
+            .find( has_update ),
+        // Full-trace updates are accepted from current authorities or when listed in an archive
+        ContentRevisionSource::FullTrace => anchors.iter()
+            .find(|anchor| !anchor.authority.archived && &anchor.authority.agent == author )
+            .or_else(|| anchors.iter().find( has_update ) ),
+    };
+
+    anchor.map(|anchor| anchor.authority.to_owned() )
+}
+
+
+#[hdk_extern]
+pub fn get_group_content_history(input: GetGroupContentInput) -> ExternResult<Vec<ContentRevision>> {
+    debug!("Get group ({}) content history: {}", input.group_id, input.content_id );
+    let group_id = input.group_id.to_owned();
+    let source = match input.full_trace {
+        None | Some(false) => ContentRevisionSource::Shortcut,
+        Some(true) => ContentRevisionSource::FullTrace,
+    };
+    let evolutions = crate::get_group_content_evolutions( input )?;
+    let anchors = history_anchors( &group_id )?;
+    let mut history = vec![];
+
+    for (index, address) in evolutions.into_iter().enumerate() {
+        let record = must_get( &resolve_action_addr( &address )? )?;
+        let author = record.action().author().to_owned();
+        let source = match index {
+            0 => ContentRevisionSource::Contribution,
+            _ => source.to_owned(),
+        };
+
+        history.push( ContentRevision {
+            authority: find_authority( &anchors, &address, &author, &source ),
+            timestamp: record.action().timestamp(),
+            address,
+            author,
+            source,
+        });
+    }
+
+    Ok( history )
+}
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use crate::hdi_extensions::{
+    // Macros
+    guest_error,
+};
+use crate::scoped_types::{
+    entry_traits::*,
+    ContributionFilter,
+};
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    create_link_input,
+    position_key_between,
+    spaced_position_keys,
+    POSITION_KEY_MAX,
+
+    // Entry Structs
+    GroupEntry,
+    ContributionsAnchorEntry,
+
+    // Link Tags
+    ContentPositionTag,
+    with_governing_revision,
+    split_governing_revision,
+    governed_payload,
+
+    // Input Structs
+    MoveGroupContentInput,
+    GetGroupContentPositionsInput,
+};
+use crate::create_if_not_exists;
+use crate::group_forks::latest_group_revision;
+use crate::group_resignations::group_resignations;
+use crate::contribution_windows::contribution_windows;
+use crate::key_succession::merged_key_anchors;
+use crate::group_capabilities::check_group_capability;
+
+
+
+/// Get the position links of an anchor for a collection
+fn position_links(anchor_addr: &EntryHash, collection: &str) -> ExternResult<Vec<(Link, ContentPositionTag)>> {
+    let links = get_links(
+        create_link_input(
+            anchor_addr,
+            &LinkTypes::ContentPosition,
+            &Some( ContentPositionTag::collection_prefix( collection ) ),
+        )?
+    )?;
+
+    Ok(
+        links.into_iter()
+            .filter_map(|link| match ContentPositionTag::from_bytes( governed_payload( &link.tag.0 ) ) {
+                Ok(tag) => Some((link, tag)),
+                Err(message) => {
+                    debug!("{}", message );
+                    None
+                },
+            })
+            .collect()
+    )
+}
+
+/// Get the tag for an archived copy of the given active anchor position link
+pub(crate) fn archived_position_tag(link: &Link) -> ExternResult<LinkTag> {
+    let (tag, group_rev) = split_governing_revision( &link.tag.0 )
+        .and_then(|(payload, group_rev)| {
+            let tag = ContentPositionTag::from_bytes( payload )?;
+            Ok( (tag.copy_of( link.create_link_hash.to_owned() ).to_bytes()?, group_rev) )
+        })
+        .map_err(|message| guest_error!(format!("Cannot archive position link ({}): {}", link.create_link_hash, message )))?;
+
+    // The copy keeps the governing revision of the original
+    Ok( with_governing_revision( tag, &group_rev ).into() )
+}
+
+/// Determine if the archived position link is a copy of the given active anchor position link
+pub(crate) fn is_position_copy(copy: &Link, link: &Link) -> bool {
+    copy.target == link.target
+        && ContentPositionTag::from_bytes( governed_payload( &copy.tag.0 ) )
+            .is_ok_and(|tag| tag.original.as_ref() == Some(&link.create_link_hash) )
+}
+
+/// Resolve the position key of each content ID in a group collection
+///
+/// Positions are read from the active and archive anchors of the latest group revision (an agent's
+/// active anchor is merged with the anchors of their key successors).  A time-bounded member's
+/// moves are only counted within their contribution window.  When there are several position links for one
+/// content ID, the latest timestamp wins and ties are broken by the highest create link hash so
+/// that concurrent moves resolve the same way for every agent.  An archived copy takes the
+/// timestamp and hash of the original link.
+///
+/// The result is sorted by position key, then by content ID.
+pub fn resolve_content_positions(
+    group_id: &ActionHash,
+    collection: &str,
+) -> ExternResult<Vec<(AnyLinkableHash, String)>> {
+    let group_rev = latest_group_revision( group_id )?;
+    let mut winners : HashMap<AnyLinkableHash, (Timestamp, ActionHash, String)> = HashMap::new();
+    let group : GroupEntry = must_get( &group_rev )?.try_into()?;
+    let windows = contribution_windows( &group, &group_resignations( &group_rev )? );
+    let mut candidates = vec![];
+
+    for anchor_addr in GroupEntry::group_auth_anchor_hashes( &group_rev )? {
+        let anchor : ContributionsAnchorEntry = must_get( &anchor_addr )?.try_into()?;
+        let window = windows.get( anchor.author() );
+
+        for anchor in merged_key_anchors( anchor )? {
+            for (link, tag) in position_links( &hash_entry( &anchor )?, collection )? {
+                if window.is_some_and(|window| !window.contains( &link.timestamp ) ) {
+                    continue;
+                }
+
+                candidates.push( (link.target, link.timestamp, link.create_link_hash, tag.key) );
+            }
+        }
+    }
+
+    for archive_addr in GroupEntry::group_auth_archive_anchor_hashes( &group_rev )? {
+        for (link, tag) in position_links( &archive_addr, collection )? {
+            let original_addr = match tag.original {
+                Some(addr) => addr,
+                None => continue,
+            };
+            let original = match get( original_addr.to_owned(), GetOptions::default() )? {
+                Some(record) => record,
+                None => {
+                    debug!("Skipping archived position; original ({}) was not found", original_addr );
+                    continue;
+                },
+            };
+
+            candidates.push( (link.target, original.action().timestamp(), original_addr, tag.key) );
+        }
+    }
+
+    for (content_id, timestamp, create_link_hash, key) in candidates {
+        let is_newer = match winners.get( &content_id ) {
+            Some((winner_timestamp, winner_hash, _)) => {
+                ( timestamp, create_link_hash.get_raw_39() )
+                    > ( *winner_timestamp, winner_hash.get_raw_39() )
+            },
+            None => true,
+        };
+
+        if is_newer {
+            winners.insert( content_id, (timestamp, create_link_hash, key) );
+        }
+    }
+
+    let mut positions : Vec<(AnyLinkableHash, String)> = winners.into_iter()
+        .map(|(content_id, (_, _, key))| (content_id, key) )
+        .collect();
+
+    positions.sort_by(|(a_id, a_key), (b_id, b_key)| {
+        a_key.cmp( b_key )
+            .then( a_id.get_raw_39().cmp( b_id.get_raw_39() ) )
+    });
+
+    Ok( positions )
+}
+
+/// Sort content targets by their resolved positions (unpositioned content goes last)
+pub fn sort_by_position(
+    targets: &mut [(AnyLinkableHash, AnyLinkableHash)],
+    positions: &[(AnyLinkableHash, String)],
+) {
+    let rank : HashMap<&AnyLinkableHash, usize> = positions.iter()
+        .enumerate()
+        .map(|(index, (content_id, _))| (content_id, index) )
+        .collect();
+
+    targets.sort_by(|(a_id, _), (b_id, _)| {
+        match ( rank.get( a_id ), rank.get( b_id ) ) {
+            (Some(a), Some(b)) => a.cmp( b ),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a_id.get_raw_39().cmp( b_id.get_raw_39() ),
+        }
+    });
+}
+
+
+#[hdk_extern]
+pub fn get_group_content_positions(input: GetGroupContentPositionsInput) -> ExternResult<Vec<(AnyLinkableHash, String)>> {
+    debug!("Get group content positions: {} ({:?})", input.group_id, input.collection );
+    resolve_content_positions( &input.group_id, input.collection.as_deref().unwrap_or("") )
+}
+
+
+/// Create a position link for a content ID on the agent's auth anchor
+fn create_position_link(
+    anchor_hash: &EntryHash,
+    group_rev: &ActionHash,
+    content_id: &AnyLinkableHash,
+    collection: &Option<String>,
+    key: String,
+) -> ExternResult<ActionHash> {
+    let tag = ContentPositionTag {
+        collection: collection.to_owned(),
+        key,
+        original: None,
+    };
+
+    debug!("Creating content position link from {} --'{:?}'--> {}", anchor_hash, tag, content_id );
+    create_link(
+        anchor_hash.to_owned(),
+        content_id.to_owned(),
+        LinkTypes::ContentPosition,
+        with_governing_revision( tag.to_bytes().map_err(|message| guest_error!(message) )?, group_rev ),
+    )
+}
+
+
+#[hdk_extern]
+pub fn move_group_content(input: MoveGroupContentInput) -> ExternResult<ActionHash> {
+    check_group_capability( &input.group_id )?;
+    debug!("Move group content {} after {:?}", input.content_id, input.after );
+    let collection = input.collection.to_owned().unwrap_or_default();
+    let filter = ContributionFilter {
+        collection: Some( collection.to_owned() ),
+        ..Default::default()
+    };
+
+    if !crate::group_content_targets_shortcuts( &input.group_id, &filter )?
+        .iter().any(|(content_id, _)| content_id == &input.content_id ) {
+        Err(guest_error!(format!("Content ({}) is not in this group collection", input.content_id )))?
+    }
+
+    let mut positions : Vec<(AnyLinkableHash, String)> = resolve_content_positions( &input.group_id, &collection )?
+        .into_iter()
+        .filter(|(content_id, _)| content_id != &input.content_id )
+        .collect();
+
+    let index = match &input.after {
+        Some(after) => {
+            let index = positions.iter()
+                .position(|(content_id, _)| content_id == after )
+                .ok_or(guest_error!(format!("Content ({}) does not have a position in this collection", after )))?;
+
+            Some( index )
+        },
+        None => None,
+    };
+    let (lower, following) = match index {
+        Some(index) => ( Some( positions[index].1.to_owned() ), &positions[index + 1..] ),
+        None => ( None, &positions[..] ),
+    };
+
+    // Skip any items that share the lower key (eg. from concurrent inserts)
+    let upper = following.iter()
+        .map(|(_, key)| key.to_owned() )
+        .find(|key| match &lower {
+            Some(lower) => key > lower,
+            None => true,
+        });
+
+    let key = position_key_between( lower.as_deref(), upper.as_deref() )
+        .map_err(|message| guest_error!(message) )?;
+
+    let group_rev = latest_group_revision( &input.group_id )?;
+    let anchor = ContributionsAnchorEntry( input.group_id.to_owned(), agent_id()? );
+    let anchor_hash = hash_entry( &anchor )?;
+
+    create_if_not_exists( &anchor )?;
+
+    if key.len() <= POSITION_KEY_MAX {
+        return create_position_link( &anchor_hash, &group_rev, &input.content_id, &input.collection, key );
+    }
+
+    // The keys around this spot are exhausted so the whole collection is re-keyed
+    debug!("Position key exceeds {} bytes; re-keying {} items", POSITION_KEY_MAX, positions.len() + 1 );
+    let insert_at = index.map( |index| index + 1 ).unwrap_or( 0 );
+    positions.insert( insert_at, (input.content_id.to_owned(), String::new()) );
+
+    let mut moved_addr = None;
+
+    for ((content_id, _), key) in positions.iter().zip( spaced_position_keys( positions.len() ) ) {
+        let action_hash = create_position_link( &anchor_hash, &group_rev, content_id, &input.collection, key )?;
+
+        if content_id == &input.content_id {
+            moved_addr = Some( action_hash );
+        }
+    }
+
+    moved_addr.ok_or(guest_error!(format!("Content ({}) was not re-keyed", input.content_id )))
+}
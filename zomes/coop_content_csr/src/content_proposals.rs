@@ -0,0 +1,170 @@
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use crate::hdi_extensions::{
+    trace_origin_root,
+    ScopedTypeConnector,
+    // Macros
+    guest_error,
+};
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    create_link_input,
+
+    // Entry Structs
+    GroupEntry,
+    ContentProposalEntry,
+
+    // Link Tags
+    ContentProposalResolutionTag,
+
+    // Input Structs
+    CreateContributionUpdateLinkInput,
+    ProposeContentUpdateInput,
+    RejectContentProposalInput,
+
+    // Output Structs
+    ContentProposalItem,
+};
+use crate::group_forks::latest_group_revision;
+
+
+
+fn is_resolved(proposal_addr: &ActionHash) -> ExternResult<bool> {
+    let links = get_links(
+        create_link_input(
+            proposal_addr,
+            &LinkTypes::ContentProposalResolution,
+            &None::<()>,
+        )?
+    )?;
+
+    Ok( !links.is_empty() )
+}
+
+/// Record the caller's decision on an open proposal
+fn resolve_proposal(proposal_addr: &ActionHash, accepted: bool, reason: String) -> ExternResult<(ContentProposalEntry, ActionHash)> {
+    let agent = agent_id()?;
+    let proposal = ContentProposalEntry::try_from_record( &must_get( proposal_addr )? )?;
+    let group_rev = latest_group_revision( &proposal.group_id )?;
+    let group = GroupEntry::try_from_record( &must_get( &group_rev )? )?;
+
+    if !group.is_contributor( &agent ) {
+        Err(guest_error!(format!("Only contributors of the group can resolve a content proposal")))?
+    }
+
+    if is_resolved( proposal_addr )? {
+        Err(guest_error!(format!("Content proposal ({}) has already been resolved", proposal_addr )))?
+    }
+
+    let tag = ContentProposalResolutionTag {
+        accepted,
+        group_rev,
+        reason,
+    }.to_bytes()
+        .map_err(|message| guest_error!(message) )?;
+
+    let link_addr = create_link( proposal_addr.to_owned(), agent, LinkTypes::ContentProposalResolution, tag )?;
+
+    Ok( ( proposal, link_addr ) )
+}
+
+
+/// Suggest a content revision to a group (any agent can make a proposal)
+#[hdk_extern]
+pub fn propose_content_update(input: ProposeContentUpdateInput) -> ExternResult<ActionHash> {
+    let proposal = ContentProposalEntry {
+        group_id: input.group_id,
+        content_id: input.content_id,
+        content_rev: input.content_rev,
+        proposed_rev: input.proposed_rev,
+    };
+    debug!("Proposing content update: {:#?}", proposal );
+
+    let action_hash = create_entry( proposal.to_input() )?;
+    create_link( proposal.group_id, action_hash.to_owned(), LinkTypes::ContentProposal, () )?;
+
+    Ok( action_hash )
+}
+
+#[hdk_extern]
+pub fn get_open_content_proposals(group_id: ActionHash) -> ExternResult<Vec<ContentProposalItem>> {
+    let links = get_links(
+        create_link_input(
+            &group_id,
+            &LinkTypes::ContentProposal,
+            &None::<()>,
+        )?
+    )?;
+    let mut proposals = vec![];
+
+    for link in links {
+        let proposal_addr = match link.target.into_action_hash() {
+            Some(addr) => addr,
+            None => {
+                debug!("WARNING: Should be unreachable because LinkTypes::ContentProposal validation only allows proposal targets");
+                continue;
+            },
+        };
+
+        if is_resolved( &proposal_addr )? {
+            continue;
+        }
+
+        let proposal = ContentProposalEntry::try_from_record( &must_get( &proposal_addr )? )?;
+
+        proposals.push( ContentProposalItem {
+            proposal: proposal_addr,
+            author: link.author,
+            timestamp: link.timestamp,
+            content_id: proposal.content_id,
+            content_rev: proposal.content_rev,
+            proposed_rev: proposal.proposed_rev,
+        });
+    }
+
+    proposals.sort_by_key(|p| p.timestamp );
+
+    Ok( proposals )
+}
+
+/// Accept a proposal by registering its revision under the caller's contributions anchor
+///
+/// The proposal must be for content in the group and its revision must descend from the content ID.
+#[hdk_extern]
+pub fn accept_content_proposal(proposal_addr: ActionHash) -> ExternResult<ActionHash> {
+    debug!("Accepting content proposal: {}", proposal_addr );
+    let proposal = ContentProposalEntry::try_from_record( &must_get( &proposal_addr )? )?;
+    let group_rev = latest_group_revision( &proposal.group_id )?;
+
+    if !crate::is_group_content( &group_rev, &proposal.content_id )? {
+        Err(guest_error!(format!("Content ID ({}) is not in group content", proposal.content_id )))?
+    }
+
+    if let Some(proposed_addr) = proposal.proposed_rev.clone().into_action_hash() {
+        if AnyLinkableHash::from( trace_origin_root( &proposed_addr )?.0 ) != proposal.content_id {
+            Err(guest_error!(format!("Proposed revision ({}) is not a revision of the content ID ({})", proposed_addr, proposal.content_id )))?
+        }
+    }
+
+    let (proposal, _) = resolve_proposal( &proposal_addr, true, String::new() )?;
+
+    crate::create_content_update_link( CreateContributionUpdateLinkInput {
+        group_id: proposal.group_id,
+        content_id: proposal.content_id,
+        content_prev: proposal.content_rev,
+        content_next: proposal.proposed_rev,
+    })
+}
+
+#[hdk_extern]
+pub fn reject_content_proposal(input: RejectContentProposalInput) -> ExternResult<ActionHash> {
+    debug!("Rejecting content proposal ({}): {}", input.proposal, input.reason );
+    let (_, link_addr) = resolve_proposal( &input.proposal, false, input.reason )?;
+
+    Ok( link_addr )
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use crate::hdi_extensions::{
+    // Macros
+    guest_error,
+};
+use crate::scoped_types::entry_traits::*;
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    // Entry Structs
+    GroupEntry,
+    ContributionsAnchorEntry,
+    ArchivedContributionsAnchorEntry,
+
+    // Link Tags
+    ContentTombstoneTag,
+    with_governing_revision,
+    split_governing_revision,
+    governed_payload,
+
+    // Input Structs
+    GetAllGroupContentInput,
+    GroupContentStateInput,
+
+    // Output Structs
+    ContentDeletion,
+    GroupContentItem,
+};
+use crate::create_if_not_exists;
+use crate::group_forks::latest_group_revision;
+use crate::group_resignations::group_resignations;
+use crate::contribution_windows::contribution_windows;
+use crate::key_succession::merged_key_anchors;
+use crate::group_capabilities::check_group_capability;
+use crate::content_history::content_registrations;
+
+
+
+/// Get the time that a tombstone link was made
+///
+/// The tag timestamp is chosen by the author, so the link's action timestamp is used instead.  An
+/// archived copy takes the action timestamp of its original link.  Returns `None` when the original
+/// of a copy cannot be found.
+fn tombstone_timestamp(link: &Link, tag: &ContentTombstoneTag) -> ExternResult<Option<Timestamp>> {
+    let original_addr = match &tag.original {
+        Some(addr) => addr,
+        None => return Ok( Some( link.timestamp ) ),
+    };
+
+    Ok(
+        match get( original_addr.to_owned(), GetOptions::default() )? {
+            Some(record) => Some( record.action().timestamp() ),
+            None => {
+                debug!("Skipping archived tombstone; original ({}) was not found", original_addr );
+                None
+            },
+        }
+    )
+}
+
+/// Resolve the deletion state of each content ID in a group
+///
+/// Tombstones are read from the active and archived anchors of the latest group revision (an
+/// agent's active anchor is merged with the anchors of their key successors).  A time-bounded
+/// member's tombstones are only counted within their contribution window.  When there are several tombstones for one
+/// content ID, the latest link timestamp wins and ties are broken by the highest author key.  Only
+/// content IDs whose winning tombstone is a deletion are returned.
+pub fn resolve_content_deletions(
+    group_id: &ActionHash,
+) -> ExternResult<HashMap<AnyLinkableHash, ContentDeletion>> {
+    let group_rev = latest_group_revision( group_id )?;
+    let group : GroupEntry = must_get( &group_rev )?.try_into()?;
+    let windows = contribution_windows( &group, &group_resignations( &group_rev )? );
+    let mut links : Vec<(AgentPubKey, Link)> = vec![];
+
+    for anchor_addr in GroupEntry::group_auth_anchor_hashes( &group_rev )? {
+        let anchor : ContributionsAnchorEntry = must_get( &anchor_addr )?.try_into()?;
+        let window = windows.get( anchor.author() );
+
+        for anchor in merged_key_anchors( anchor )? {
+            for link in anchor.tombstone_links()? {
+                if window.is_none_or(|window| window.contains( &link.timestamp ) ) {
+                    links.push( (anchor.author().to_owned(), link) );
+                }
+            }
+        }
+    }
+
+    for archive_addr in GroupEntry::group_auth_archive_anchor_hashes( &group_rev )? {
+        let anchor : ArchivedContributionsAnchorEntry = must_get( &archive_addr )?.try_into()?;
+
+        for link in anchor.tombstone_links()? {
+            links.push( (anchor.author().to_owned(), link) );
+        }
+    }
+
+    let mut winners : HashMap<AnyLinkableHash, (bool, Timestamp, AgentPubKey)> = HashMap::new();
+
+    for (author, link) in links {
+        let tag = match ContentTombstoneTag::from_bytes( governed_payload( &link.tag.0 ) ) {
+            Ok(tag) => tag,
+            Err(message) => {
+                debug!("{}", message );
+                continue;
+            },
+        };
+        let timestamp = match tombstone_timestamp( &link, &tag )? {
+            Some(timestamp) => timestamp,
+            None => continue,
+        };
+
+        let is_newer = match winners.get( &link.target ) {
+            Some((_, winner_timestamp, winner_author)) => {
+                ( timestamp, author.get_raw_39() )
+                    > ( *winner_timestamp, winner_author.get_raw_39() )
+            },
+            None => true,
+        };
+
+        if is_newer {
+            winners.insert( link.target, (tag.deleted, timestamp, author) );
+        }
+    }
+
+    Ok(
+        winners.into_iter()
+            .filter(|(_, (deleted, _, _))| *deleted )
+            .map(|(content_id, (_, timestamp, author))| (content_id, ContentDeletion {
+                author,
+                timestamp,
+            }))
+            .collect()
+    )
+}
+
+/// Get the tag for an archived copy of the given active anchor tombstone link
+pub(crate) fn archived_tombstone_tag(link: &Link) -> ExternResult<LinkTag> {
+    let (tag, group_rev) = split_governing_revision( &link.tag.0 )
+        .and_then(|(payload, group_rev)| Ok( (ContentTombstoneTag::from_bytes( payload )?, group_rev) ) )
+        .map_err(|message| guest_error!(format!("Cannot archive tombstone link ({}): {}", link.create_link_hash, message )))?;
+
+    // The copy keeps the governing revision of the original
+    Ok( with_governing_revision( tag.copy_of( link.create_link_hash.to_owned() ).to_bytes(), &group_rev ).into() )
+}
+
+/// Determine if the archived tombstone link is a copy of the given active anchor tombstone link
+pub(crate) fn is_tombstone_copy(copy: &Link, link: &Link) -> bool {
+    copy.target == link.target
+        && ContentTombstoneTag::from_bytes( governed_payload( &copy.tag.0 ) )
+            .is_ok_and(|tag| tag.original.as_ref() == Some(&link.create_link_hash) )
+}
+
+fn create_tombstone_link(input: GroupContentStateInput, deleted: bool) -> ExternResult<ActionHash> {
+    let author = agent_id()?;
+    let group_rev = latest_group_revision( &input.group_id )?;
+    let group : GroupEntry = must_get( &group_rev )?.try_into()?;
+
+    if !group.is_contributor( &author ) {
+        Err(guest_error!(format!("Only contributors of the group can change the deletion state of its content")))?
+    }
+
+    let tag = ContentTombstoneTag {
+        deleted,
+        timestamp: sys_time()?,
+        original: None,
+    };
+    let anchor = ContributionsAnchorEntry( input.group_id, author );
+    let anchor_hash = hash_entry( &anchor )?;
+
+    create_if_not_exists( &anchor )?;
+
+    debug!("Creating content tombstone link from {} --'{:?}'--> {}", anchor_hash, tag, input.content_id );
+    create_link( anchor_hash, input.content_id, LinkTypes::ContentTombstone, with_governing_revision( tag.to_bytes(), &group_rev ) )
+}
+
+
+#[hdk_extern]
+pub fn delete_group_content(input: GroupContentStateInput) -> ExternResult<ActionHash> {
+    debug!("Delete group content: {}", input.content_id );
+    check_group_capability( &input.group_id )?;
+    create_tombstone_link( input, true )
+}
+
+#[hdk_extern]
+pub fn undelete_group_content(input: GroupContentStateInput) -> ExternResult<ActionHash> {
+    debug!("Undelete group content: {}", input.content_id );
+    check_group_capability( &input.group_id )?;
+    create_tombstone_link( input, false )
+}
+
+
+#[hdk_extern]
+pub fn get_all_group_content(input: GetAllGroupContentInput) -> ExternResult<Vec<GroupContentItem>> {
+    let group_id = input.group_id.to_owned();
+    let targets = crate::get_all_group_content_targets( input )?;
+    let mut deletions = resolve_content_deletions( &group_id )?;
+    let mut registrations = content_registrations( &group_id )?;
+
+    Ok(
+        targets.into_iter()
+            .map(|(content_id, latest)| GroupContentItem {
+                deleted: deletions.remove( &content_id ),
+                anchors: registrations.remove( &content_id ).unwrap_or_default(),
+                content_id,
+                latest,
+            })
+            .collect()
+    )
+}
+
+#[hdk_extern]
+pub fn get_group_content_deletion(input: GroupContentStateInput) -> ExternResult<Option<ContentDeletion>> {
+    Ok( resolve_content_deletions( &input.group_id )?.remove( &input.content_id ) )
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use crate::hdk::prelude::*;
+use crate::scoped_types::entry_traits::*;
+use coop_content_sdk::{
+    // Entry Structs
+    GroupEntry,
+    ContributionsAnchorEntry,
+    MembershipWindow,
+};
+
+
+
+/// Get the window in which each time-bounded contributor's links are counted
+///
+/// A member's window comes from the group's membership windows and is closed early by a
+/// resignation.  Contributors without a window are not included.
+pub fn contribution_windows(
+    group: &GroupEntry,
+    resignations: &HashMap<AgentPubKey, Timestamp>,
+) -> HashMap<AgentPubKey, MembershipWindow> {
+    let mut windows : HashMap<AgentPubKey, MembershipWindow> = group.membership_windows.iter()
+        .filter(|window| group.is_member( &window.agent ) )
+        .map(|window| (window.agent.to_owned(), window.to_owned()) )
+        .collect();
+
+    for (agent, resigned_at) in resignations.iter() {
+        let window = windows.entry( agent.to_owned() )
+            .or_insert( MembershipWindow {
+                agent: agent.to_owned(),
+                valid_from: None,
+                valid_until: None,
+            });
+
+        window.valid_until = Some( match window.valid_until {
+            Some(valid_until) => std::cmp::min( valid_until, *resigned_at ),
+            None => *resigned_at,
+        });
+    }
+
+    windows
+}
+
+/// Get the group contributors that are not time-bounded
+pub fn unbounded_contributors(
+    group: &GroupEntry,
+    windows: &HashMap<AgentPubKey, MembershipWindow>,
+) -> Vec<AgentPubKey> {
+    group.contributors().into_iter()
+        .filter(|agent| !windows.contains_key( agent ) )
+        .collect()
+}
+
+/// Get the update targets that a time-bounded contributor made within their window
+///
+/// These are treated the same as the update targets of an archived anchor.
+pub fn bounded_update_targets(
+    anchor: &ContributionsAnchorEntry,
+    window: &MembershipWindow,
+) -> ExternResult<Vec<ActionHash>> {
+    Ok(
+        anchor.update_links()?.into_iter()
+            .filter(|link| window.contains( &link.timestamp ) )
+            .filter_map(|link| link.target.into_action_hash() )
+            .collect()
+    )
+}
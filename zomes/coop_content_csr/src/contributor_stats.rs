@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    must_get,
+};
+use crate::scoped_types::{
+    ContributionFilter,
+    entry_traits::*,
+};
+use coop_content_sdk::{
+    // Entry Structs
+    GroupEntry,
+    ContributionsAnchorEntry,
+    ContributionAnchors,
+
+    // Output Structs
+    ContributorStats,
+};
+use crate::group_forks::latest_group_revision;
+
+
+
+/// Replace archived link copies with the original links from the agent's active anchor
+///
+/// Archived copies are created by an admin when the contributor is removed, so their timestamps
+/// are the time of archiving rather than the time of the contribution.  Copies without a matching
+/// original are kept as-is.
+pub(crate) fn original_links(copies: Vec<Link>, originals: &[Link]) -> Vec<Link> {
+    copies.into_iter()
+        .map(|copy| {
+            originals.iter()
+                .find(|link| link.target == copy.target && link.tag == copy.tag )
+                .cloned()
+                .unwrap_or( copy )
+        })
+        .collect()
+}
+
+
+#[hdk_extern]
+pub fn get_group_contributor_stats(group_id: ActionHash) -> ExternResult<Vec<ContributorStats>> {
+    debug!("Get group contributor stats: {}", group_id );
+    let group_rev = latest_group_revision( &group_id )?;
+    let heads : Vec<(AnyLinkableHash, AnyLinkableHash)> = crate::group_content_targets_shortcuts( &group_id, &ContributionFilter::default() )?;
+    let anchor_addrs = GroupEntry::group_auth_anchor_hashes( &group_rev )?.into_iter()
+        .chain( GroupEntry::group_auth_archive_anchor_hashes( &group_rev )? );
+    let mut stats = vec![];
+
+    for anchor_addr in anchor_addrs {
+        let anchor : ContributionAnchors = must_get( &anchor_addr )?.try_into()?;
+        let (create_links, update_links) = match &anchor {
+            ContributionAnchors::Active(anchor) => ( anchor.create_links()?, anchor.update_links()? ),
+            ContributionAnchors::Archive(anchor) => {
+                let active_anchor = ContributionsAnchorEntry( group_id.to_owned(), anchor.author().to_owned() );
+                (
+                    original_links( anchor.create_links()?, &active_anchor.create_links()? ),
+                    original_links( anchor.update_links()?, &active_anchor.update_links()? ),
+                )
+            },
+        };
+
+        let created : HashSet<&AnyLinkableHash> = create_links.iter()
+            .map(|link| &link.target )
+            .collect();
+        let updated : HashSet<&AnyLinkableHash> = update_links.iter()
+            .map(|link| &link.target )
+            .collect();
+        let authored_heads = heads.iter()
+            .filter(|(content_id, latest)| match content_id == latest {
+                true => created.contains( latest ),
+                false => updated.contains( latest ),
+            })
+            .count();
+
+        let timestamps = create_links.iter()
+            .chain( update_links.iter() )
+            .map(|link| link.timestamp );
+
+        stats.push( ContributorStats {
+            agent: anchor.author().to_owned(),
+            anchor: anchor_addr,
+            archived: anchor.is_archive(),
+            contributions: create_links.len() as u64,
+            updates: update_links.len() as u64,
+            first_activity: timestamps.clone().min(),
+            last_activity: timestamps.max(),
+            heads: authored_heads as u64,
+        });
+    }
+
+    Ok( stats )
+}
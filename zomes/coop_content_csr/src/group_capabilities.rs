@@ -0,0 +1,232 @@
+use std::collections::{
+    BTreeSet,
+    HashSet,
+};
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use crate::hdi_extensions::{
+    ScopedTypeConnector,
+    // Macros
+    guest_error,
+};
+use coop_content_sdk::{
+    GROUP_CAPABILITY_FUNCTIONS,
+
+    // Entry Structs
+    GroupEntry,
+
+    // Input Structs
+    GrantGroupCapabilityInput,
+    RevokeGroupCapabilityInput,
+
+    // Output Structs
+    GroupCapabilityTag,
+    GroupCapabilityClaim,
+};
+use crate::group_forks::latest_group_revision;
+use crate::group_resignations::group_resignations;
+use crate::contribution_windows::contribution_windows;
+
+
+
+/// Check if an agent is currently a contributor (within their window) of a group's latest revision
+fn is_current_contributor(group_id: &ActionHash, agent: &AgentPubKey) -> ExternResult<bool> {
+    let group_rev = latest_group_revision( group_id )?;
+    let group = GroupEntry::try_from_record( &must_get( &group_rev )? )?;
+
+    if !group.is_contributor( agent ) {
+        return Ok( false );
+    }
+
+    let windows = contribution_windows( &group, &group_resignations( &group_rev )? );
+
+    Ok( match windows.get( agent ) {
+        Some(window) => window.contains( &sys_time()? ),
+        None => true,
+    })
+}
+
+/// Check that a remote caller's capability grant (if any) is for the given group
+///
+/// Calls made by the chain author are always allowed.  Remote callers must still be contributors
+/// of the group so that removing a member also removes the use of their grants.
+pub fn check_group_capability(group_id: &ActionHash) -> ExternResult<()> {
+    let call_info = call_info()?;
+
+    match call_info.cap_grant {
+        CapGrant::ChainAuthor(_) => Ok(()),
+        CapGrant::RemoteAgent(grant) => match GroupCapabilityTag::from_tag( &grant.tag ) {
+            Ok(tag) if &tag.group_id == group_id => {
+                if !is_current_contributor( group_id, &call_info.provenance )? {
+                    Err(guest_error!(format!("Caller ({}) is no longer a contributor of group ({})", call_info.provenance, group_id )))?
+                }
+
+                Ok(())
+            },
+            _ => Err(guest_error!(format!("Capability grant '{}' does not allow calls for group ({})", grant.tag, group_id ))),
+        },
+    }
+}
+
+/// Get the addresses of the caller's cap grants that have been deleted
+fn deleted_grants() -> ExternResult<HashSet<ActionHash>> {
+    Ok(
+        query( ChainQueryFilter::new().action_type( ActionType::Delete ) )?.into_iter()
+            .filter_map(|record| match record.action() {
+                Action::Delete(delete) => Some( delete.deletes_address.to_owned() ),
+                _ => None,
+            })
+            .collect()
+    )
+}
+
+
+/// Allow a contributor to call some of the caller's functions for a group
+///
+/// The claim is delivered to the grantee when they are online; otherwise it must be passed to
+/// their [`receive_group_capability`] by other means.
+#[hdk_extern]
+pub fn grant_group_capability(input: GrantGroupCapabilityInput) -> ExternResult<GroupCapabilityClaim> {
+    let agent = agent_id()?;
+    let group_rev = latest_group_revision( &input.group_id )?;
+    let group = GroupEntry::try_from_record( &must_get( &group_rev )? )?;
+    debug!("Granting group ({}) capabilities to {}: {:?}", input.group_id, input.grantee, input.functions );
+
+    if !group.is_contributor( &agent ) || !group.is_contributor( &input.grantee ) {
+        Err(guest_error!(format!("Group capabilities can only be granted between contributors of the group")))?
+    }
+
+    if input.functions.is_empty() {
+        Err(guest_error!(format!("A group capability must grant at least one function")))?
+    }
+
+    if let Some(name) = input.functions.iter().find(|name| !GROUP_CAPABILITY_FUNCTIONS.contains( &name.as_str() ) ) {
+        Err(guest_error!(format!("Function '{}' cannot be granted with a group capability", name )))?
+    }
+
+    let zome_name = zome_info()?.name;
+    let tag = GroupCapabilityTag {
+        group_id: input.group_id.to_owned(),
+        functions: input.functions.to_owned(),
+    };
+    let secret = generate_cap_secret()?;
+    let functions : BTreeSet<(ZomeName, FunctionName)> = input.functions.iter()
+        .map(|name| ( zome_name.to_owned(), name.to_owned().into() ) )
+        .collect();
+
+    create_cap_grant( CapGrantEntry {
+        tag: tag.to_tag(),
+        access: CapAccess::Assigned {
+            secret: secret.to_owned(),
+            assignees: BTreeSet::from([ input.grantee.to_owned() ]),
+        },
+        functions: GrantedFunctions::Listed( functions ),
+    })?;
+
+    let claim = GroupCapabilityClaim {
+        group_id: input.group_id,
+        grantor: agent,
+        functions: input.functions,
+        secret,
+    };
+
+    match call_remote( input.grantee.to_owned(), zome_name, "receive_group_capability".into(), None, claim.to_owned() ) {
+        Ok(ZomeCallResponse::Ok(_)) => debug!("Delivered group capability claim to {}", input.grantee ),
+        response => debug!("Could not deliver group capability claim to {}: {:?}", input.grantee, response ),
+    }
+
+    Ok( claim )
+}
+
+/// Store a group capability claim from its grantor
+#[hdk_extern]
+pub fn receive_group_capability(claim: GroupCapabilityClaim) -> ExternResult<ActionHash> {
+    let provenance = call_info()?.provenance;
+
+    if provenance != claim.grantor && provenance != agent_id()? {
+        Err(guest_error!(format!("A group capability claim can only be delivered by its grantor ({})", claim.grantor )))?
+    }
+
+    if !is_current_contributor( &claim.group_id, &claim.grantor )? {
+        Err(guest_error!(format!("Grantor ({}) is not a contributor of group ({})", claim.grantor, claim.group_id )))?
+    }
+
+    let tag = GroupCapabilityTag {
+        group_id: claim.group_id,
+        functions: claim.functions,
+    };
+
+    create_cap_claim( CapClaimEntry::new( tag.to_tag(), claim.grantor, claim.secret ) )
+}
+
+#[hdk_extern]
+pub fn revoke_group_capability(input: RevokeGroupCapabilityInput) -> ExternResult<Vec<ActionHash>> {
+    debug!("Revoking group ({}) capabilities from {}", input.group_id, input.grantee );
+    let deleted = deleted_grants()?;
+    let records = query(
+        ChainQueryFilter::new()
+            .entry_type( EntryType::CapGrant )
+            .include_entries( true )
+    )?;
+    let mut revoked = vec![];
+
+    for record in records {
+        if deleted.contains( record.action_address() ) {
+            continue;
+        }
+
+        let grant = match record.entry().as_option() {
+            Some(Entry::CapGrant(grant)) => grant,
+            _ => continue,
+        };
+        let is_group_grant = match GroupCapabilityTag::from_tag( &grant.tag ) {
+            Ok(tag) => tag.group_id == input.group_id,
+            Err(_) => false,
+        };
+        let is_assigned = match &grant.access {
+            CapAccess::Assigned { assignees, .. } => assignees.contains( &input.grantee ),
+            _ => false,
+        };
+
+        if is_group_grant && is_assigned {
+            revoked.push( delete_cap_grant( record.action_address().to_owned() )? );
+        }
+    }
+
+    Ok( revoked )
+}
+
+#[hdk_extern]
+pub fn get_group_capability_claims(group_id: ActionHash) -> ExternResult<Vec<GroupCapabilityClaim>> {
+    let records = query(
+        ChainQueryFilter::new()
+            .entry_type( EntryType::CapClaim )
+            .include_entries( true )
+    )?;
+    let mut claims = vec![];
+
+    for record in records {
+        let claim = match record.entry().as_option() {
+            Some(Entry::CapClaim(claim)) => claim,
+            _ => continue,
+        };
+        let tag = match GroupCapabilityTag::from_tag( &claim.tag ) {
+            Ok(tag) => tag,
+            Err(_) => continue,
+        };
+
+        if tag.group_id == group_id {
+            claims.push( GroupCapabilityClaim {
+                group_id: tag.group_id,
+                grantor: claim.grantor.to_owned(),
+                functions: tag.functions,
+                secret: claim.secret.to_owned(),
+            });
+        }
+    }
+
+    Ok( claims )
+}
@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    must_get,
+};
+use crate::hdi_extensions::{
+    ScopedTypeConnector,
+};
+use crate::scoped_types::entry_traits::*;
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    create_link_input,
+
+    // Entry Structs
+    GroupEntry,
+    ContributionsAnchorEntry,
+    ContributionAnchors,
+
+    // Input Structs
+    GroupChangesSinceInput,
+
+    // Output Structs
+    GroupChangeCursor,
+    GroupChanges,
+    GroupGraphLink,
+    GroupGraphLinkType,
+    GroupGraphRevision,
+    GroupMembershipChange,
+    GroupMembershipChangeKind,
+};
+use crate::graph_link;
+use crate::group_forks::canonical_group_revisions;
+use crate::key_succession::merged_key_anchors;
+use crate::contributor_stats::original_links;
+use crate::content_tombstones::is_tombstone_copy;
+use crate::content_ordering::is_position_copy;
+
+
+
+fn is_new(cursor: &Option<GroupChangeCursor>, timestamp: &Timestamp, hash: &ActionHash) -> bool {
+    match cursor {
+        Some(cursor) => cursor.is_before( timestamp, hash ),
+        None => true,
+    }
+}
+
+fn sort_links(links: &mut [GroupGraphLink]) {
+    links.sort_by(|a, b| {
+        a.timestamp.cmp( &b.timestamp )
+            .then( a.create_link_hash.get_raw_39().cmp( b.create_link_hash.get_raw_39() ) )
+    });
+}
+
+/// Replace archived copies with the original link that they reference (when it can be found)
+fn referenced_originals(
+    copies: Vec<Link>,
+    originals: &[Link],
+    is_copy: fn(&Link, &Link) -> bool,
+) -> Vec<Link> {
+    copies.into_iter()
+        .map(|copy| {
+            originals.iter()
+                .find(|link| is_copy( &copy, link ) )
+                .cloned()
+                .unwrap_or( copy )
+        })
+        .collect()
+}
+
+/// Get the content links of an anchor as (link type, base, link)
+///
+/// An active anchor is merged with the anchors of its agent's key successors.  The copies on an
+/// archive anchor are replaced by their original links so that archiving a contributor does not
+/// repeat their changes.
+fn anchor_changes(
+    group_id: &ActionHash,
+    anchor_addr: &EntryHash,
+) -> ExternResult<Vec<(GroupGraphLinkType, EntryHash, Link)>> {
+    let mut changes = vec![];
+
+    match must_get( anchor_addr )?.try_into()? {
+        ContributionAnchors::Active(anchor) => {
+            for anchor in merged_key_anchors( anchor )? {
+                let base = anchor.base_hash()?;
+                let links = anchor.create_links()?.into_iter().map(|link| (GroupGraphLinkType::Contribution, link) )
+                    .chain( anchor.update_links()?.into_iter().map(|link| (GroupGraphLinkType::ContributionUpdate, link) ) )
+                    .chain( anchor.tombstone_links()?.into_iter().map(|link| (GroupGraphLinkType::ContentTombstone, link) ) )
+                    .chain( anchor.position_links()?.into_iter().map(|link| (GroupGraphLinkType::ContentPosition, link) ) );
+
+                for (link_type, link) in links {
+                    changes.push( (link_type, base.to_owned(), link) );
+                }
+            }
+        },
+        ContributionAnchors::Archive(anchor) => {
+            let active_anchor = ContributionsAnchorEntry( group_id.to_owned(), anchor.author().to_owned() );
+            let active_addr = active_anchor.base_hash()?;
+            let active_creates = active_anchor.create_links()?;
+            let active_updates = active_anchor.update_links()?;
+            let active_tombstones = active_anchor.tombstone_links()?;
+            let active_positions = active_anchor.position_links()?;
+            let active_hashes : HashSet<ActionHash> = active_creates.iter()
+                .chain( active_updates.iter() )
+                .chain( active_tombstones.iter() )
+                .chain( active_positions.iter() )
+                .map(|link| link.create_link_hash.to_owned() )
+                .collect();
+
+            let links = original_links( anchor.create_links()?, &active_creates ).into_iter()
+                .map(|link| (GroupGraphLinkType::Contribution, link) )
+                .chain(
+                    original_links( anchor.update_links()?, &active_updates ).into_iter()
+                        .map(|link| (GroupGraphLinkType::ContributionUpdate, link) )
+                )
+                .chain(
+                    referenced_originals( anchor.tombstone_links()?, &active_tombstones, is_tombstone_copy ).into_iter()
+                        .map(|link| (GroupGraphLinkType::ContentTombstone, link) )
+                )
+                .chain(
+                    referenced_originals( anchor.position_links()?, &active_positions, is_position_copy ).into_iter()
+                        .map(|link| (GroupGraphLinkType::ContentPosition, link) )
+                );
+
+            for (link_type, link) in links {
+                // Copies without a matching original stay on the archive anchor
+                let base = match active_hashes.contains( &link.create_link_hash ) {
+                    true => active_addr.to_owned(),
+                    false => anchor_addr.to_owned(),
+                };
+                changes.push( (link_type, base, link) );
+            }
+        },
+    }
+
+    Ok( changes )
+}
+
+/// Move the cursor forward to the given change (if it is later)
+fn advance(cursor: &mut Option<GroupChangeCursor>, timestamp: &Timestamp, hash: &ActionHash) {
+    if is_new( cursor, timestamp, hash ) {
+        *cursor = Some( GroupChangeCursor {
+            timestamp: *timestamp,
+            hash: hash.to_owned(),
+        });
+    }
+}
+
+
+/// Get the contribution, update, tombstone and position links, group revisions and membership
+/// changes made after a cursor
+///
+/// Links are read from the anchors of the latest group revision.  Archive copies are reported as
+/// the original link that they were copied from, so removing a contributor does not repeat their
+/// changes.
+#[hdk_extern]
+pub fn get_group_changes_since(input: GroupChangesSinceInput) -> ExternResult<GroupChanges> {
+    debug!("Get group ({}) changes since: {:?}", input.group_id, input.cursor );
+    let since = input.cursor;
+    let mut cursor = since.to_owned();
+    let mut revisions = vec![];
+    let mut membership = vec![];
+    let mut previous_group : Option<GroupEntry> = None;
+
+    let group_revs = canonical_group_revisions( &input.group_id )?;
+
+    for group_rev in group_revs.iter() {
+        let record = must_get( group_rev )?;
+        let group = GroupEntry::try_from_record( &record )?;
+        let timestamp = record.action().timestamp();
+
+        if is_new( &since, &timestamp, group_rev ) {
+            // The contributors of the first revision are reported as added by it
+            let (added, removed) = match &previous_group {
+                Some(previous) => {
+                    let diff = previous.contributors_diff( &group );
+                    ( diff.added, diff.removed )
+                },
+                None => ( group.contributors(), vec![] ),
+            };
+            let changes = added.into_iter()
+                .map(|agent| (agent, GroupMembershipChangeKind::Added) )
+                .chain( removed.into_iter().map(|agent| (agent, GroupMembershipChangeKind::Removed) ) );
+
+            for (agent, kind) in changes {
+                membership.push( GroupMembershipChange {
+                    agent,
+                    kind,
+                    revision: group_rev.to_owned(),
+                    timestamp,
+                });
+            }
+
+            revisions.push( GroupGraphRevision {
+                action: group_rev.to_owned(),
+                previous: match record.action() {
+                    Action::Update(update) => Some( update.original_action_address.to_owned() ),
+                    _ => None,
+                },
+                author: record.action().author().to_owned(),
+                timestamp,
+                group: group.to_owned(),
+            });
+            advance( &mut cursor, &timestamp, group_rev );
+        }
+
+        let resignation_links = get_links(
+            create_link_input(
+                group_rev,
+                &LinkTypes::GroupResignation,
+                &None::<()>,
+            )?
+        )?;
+
+        for link in resignation_links {
+            if !is_new( &since, &link.timestamp, &link.create_link_hash ) {
+                continue;
+            }
+
+            if let Some(agent) = link.target.into_agent_pub_key() {
+                membership.push( GroupMembershipChange {
+                    agent,
+                    kind: GroupMembershipChangeKind::Resigned,
+                    revision: group_rev.to_owned(),
+                    timestamp: link.timestamp,
+                });
+                advance( &mut cursor, &link.timestamp, &link.create_link_hash );
+            }
+        }
+
+        previous_group = Some( group );
+    }
+
+    let mut contributions = vec![];
+    let mut updates = vec![];
+    let mut tombstones = vec![];
+    let mut positions = vec![];
+    let mut reported : HashSet<ActionHash> = HashSet::new();
+
+    if let Some(group_rev) = group_revs.last() {
+        let anchor_addrs = GroupEntry::group_auth_anchor_hashes( group_rev )?.into_iter()
+            .chain( GroupEntry::group_auth_archive_anchor_hashes( group_rev )? );
+
+        for anchor_addr in anchor_addrs {
+            for (link_type, base, link) in anchor_changes( &input.group_id, &anchor_addr )? {
+                if !is_new( &since, &link.timestamp, &link.create_link_hash )
+                    || !reported.insert( link.create_link_hash.to_owned() ) {
+                    continue;
+                }
+
+                let changes = match link_type {
+                    GroupGraphLinkType::Contribution => &mut contributions,
+                    GroupGraphLinkType::ContributionUpdate => &mut updates,
+                    GroupGraphLinkType::ContentTombstone => &mut tombstones,
+                    GroupGraphLinkType::ContentPosition => &mut positions,
+                    _ => continue,
+                };
+
+                advance( &mut cursor, &link.timestamp, &link.create_link_hash );
+                changes.push( graph_link( link_type, base, link ) );
+            }
+        }
+    }
+
+    sort_links( &mut contributions );
+    sort_links( &mut updates );
+    sort_links( &mut tombstones );
+    sort_links( &mut positions );
+    membership.sort_by_key(|change| change.timestamp );
+
+    Ok( GroupChanges {
+        contributions,
+        updates,
+        tombstones,
+        positions,
+        revisions,
+        membership,
+        cursor,
+    })
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use crate::hdi_extensions::{
+    ScopedTypeConnector,
+    // Macros
+    guest_error,
+};
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    create_link_input,
+    group_directory_name_anchor,
+    group_directory_tag_anchor,
+    group_directory_group_anchor,
+    GROUP_DIRECTORY_SHARD_LENGTH,
+
+    // Entry Structs
+    GroupEntry,
+
+    // Link Tags
+    GroupDirectoryTag,
+
+    // Input Structs
+    ListPublicGroupInput,
+
+    // Output Structs
+    PublicGroupListing,
+};
+use crate::group_forks::latest_group_revision;
+
+
+
+fn directory_links(anchor: &EntryHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        create_link_input(
+            anchor,
+            &LinkTypes::GroupDirectory,
+            &None::<()>,
+        )?
+    )
+}
+
+fn listing_from_link(link: Link) -> Option<PublicGroupListing> {
+    let group_id = link.target.into_action_hash()?;
+    let listing = match GroupDirectoryTag::from_bytes( &link.tag.0 ) {
+        Ok(listing) => listing,
+        Err(message) => {
+            debug!("Skipping group directory link with malformed tag: {}", message );
+            return None;
+        },
+    };
+
+    Some( PublicGroupListing {
+        group_id,
+        group_rev: listing.group_rev,
+        name: listing.name,
+        tags: listing.tags,
+        author: link.author,
+        timestamp: link.timestamp,
+    })
+}
+
+/// Keep only the latest listing of each group (sorted by name)
+fn latest_listings<I>(listings: I) -> Vec<PublicGroupListing>
+where
+    I: IntoIterator<Item = PublicGroupListing>,
+{
+    let mut latest : HashMap<ActionHash, PublicGroupListing> = HashMap::new();
+
+    for listing in listings {
+        match latest.get( &listing.group_id ) {
+            Some(current) if current.timestamp >= listing.timestamp => continue,
+            _ => latest.insert( listing.group_id.to_owned(), listing ),
+        };
+    }
+
+    let mut listings : Vec<PublicGroupListing> = latest.into_values().collect();
+    listings.sort_by(|a, b| {
+        a.name.to_lowercase().cmp( &b.name.to_lowercase() )
+            .then( a.group_id.get_raw_39().cmp( b.group_id.get_raw_39() ) )
+    });
+
+    listings
+}
+
+/// Delete every directory link (group, name and tags) of a group's listings
+fn delete_listings(group_id: &ActionHash) -> ExternResult<Vec<ActionHash>> {
+    let mut deleted = vec![];
+
+    for link in directory_links( &group_directory_group_anchor( group_id )? )? {
+        if link.target != AnyLinkableHash::from( group_id.to_owned() ) {
+            continue;
+        }
+
+        let listing = match GroupDirectoryTag::from_bytes( &link.tag.0 ) {
+            Ok(listing) => listing,
+            Err(message) => {
+                debug!("Skipping group directory link with malformed tag: {}", message );
+                continue;
+            },
+        };
+
+        for anchor in listing.anchors()? {
+            for anchor_link in directory_links( &anchor )? {
+                if anchor_link.target == link.target && anchor_link.tag == link.tag {
+                    deleted.push( delete_link( anchor_link.create_link_hash )? );
+                }
+            }
+        }
+
+        deleted.push( delete_link( link.create_link_hash )? );
+    }
+
+    Ok( deleted )
+}
+
+
+/// List a group in the public group directory under its name and tags
+///
+/// Any previous listing of the group is replaced.
+#[hdk_extern]
+pub fn list_public_group(input: ListPublicGroupInput) -> ExternResult<Vec<ActionHash>> {
+    let group_rev = latest_group_revision( &input.group_id )?;
+    let group = GroupEntry::try_from_record( &must_get( &group_rev )? )?;
+    debug!("Listing group revision ({}) in the group directory as: {}", group_rev, input.name );
+
+    if !group.is_admin( &agent_id()? ) {
+        Err(guest_error!(format!("Only admins of the group can list it in the group directory")))?
+    }
+
+    let mut tags : Vec<String> = input.tags.iter()
+        .map(|tag| tag.trim().to_lowercase() )
+        .collect();
+    tags.sort();
+    tags.dedup();
+
+    let listing = GroupDirectoryTag {
+        group_rev,
+        name: input.name.trim().to_string(),
+        tags,
+    };
+    let tag = listing.to_bytes()
+        .map_err(|message| guest_error!(message) )?;
+
+    delete_listings( &input.group_id )?;
+
+    let mut link_addrs = vec![];
+    let mut anchors = listing.anchors()?;
+    anchors.push( group_directory_group_anchor( &input.group_id )? );
+
+    for anchor in anchors {
+        link_addrs.push( create_link(
+            anchor,
+            input.group_id.to_owned(),
+            LinkTypes::GroupDirectory,
+            tag.to_owned(),
+        )? );
+    }
+
+    Ok( link_addrs )
+}
+
+#[hdk_extern]
+pub fn delist_public_group(group_id: ActionHash) -> ExternResult<Vec<ActionHash>> {
+    debug!("Delisting group ({}) from the group directory", group_id );
+    delete_listings( &group_id )
+}
+
+/// Find listed groups whose names start with the given prefix (case-insensitive)
+#[hdk_extern]
+pub fn search_public_groups(name_prefix: String) -> ExternResult<Vec<PublicGroupListing>> {
+    let prefix = name_prefix.trim().to_lowercase();
+
+    if prefix.chars().count() < GROUP_DIRECTORY_SHARD_LENGTH {
+        Err(guest_error!(format!("Group name search must be at least {} characters", GROUP_DIRECTORY_SHARD_LENGTH )))?
+    }
+
+    let listings = directory_links( &group_directory_name_anchor( &prefix )? )?.into_iter()
+        .filter_map( listing_from_link )
+        .filter(|listing| listing.name.to_lowercase().starts_with( &prefix ) );
+
+    Ok( latest_listings( listings ) )
+}
+
+#[hdk_extern]
+pub fn get_public_groups_by_tag(tag: String) -> ExternResult<Vec<PublicGroupListing>> {
+    let tag = tag.trim().to_lowercase();
+    let listings = directory_links( &group_directory_tag_anchor( &tag )? )?.into_iter()
+        .filter_map( listing_from_link );
+
+    Ok( latest_listings( listings ) )
+}
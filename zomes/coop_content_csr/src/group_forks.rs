@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use crate::hdi_extensions::{
+    trace_origin,
+    ScopedTypeConnector,
+    // Macros
+    guest_error,
+};
+use crate::scoped_types::entry_traits::*;
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    create_link_input,
+    canonical_group_branch,
+    merge_group_entries,
+
+    // Entry Structs
+    GroupEntry,
+
+    // Output Structs
+    GroupBranch,
+    GroupForks,
+};
+use crate::create_group_revision_links;
+use crate::group_resignations::group_resignations;
+use crate::contribution_windows::contribution_windows;
+use crate::group_keys::update_group_key;
+
+
+
+fn revision_updates(group_rev: &ActionHash) -> ExternResult<Vec<ActionHash>> {
+    match get_details( group_rev.to_owned(), GetOptions::default() )? {
+        Some(Details::Record(details)) => Ok(
+            details.updates.iter()
+                .map(|update| update.action_address().to_owned() )
+                .collect()
+        ),
+        _ => Err(guest_error!(format!("Group revision not found: {}", group_rev ))),
+    }
+}
+
+/// Get the revisions registered in a group's revision index
+fn indexed_revisions(group_id: &ActionHash) -> ExternResult<Vec<ActionHash>> {
+    let links = get_links(
+        create_link_input(
+            group_id,
+            &LinkTypes::GroupRevision,
+            &None::<()>,
+        )?
+    )?;
+
+    Ok(
+        links.into_iter()
+            .filter_map(|link| link.target.into_action_hash() )
+            .collect()
+    )
+}
+
+/// Check if a branch head has been merged by a later revision
+fn is_merged_head(head: &ActionHash) -> ExternResult<bool> {
+    let links = get_links(
+        create_link_input(
+            head,
+            &LinkTypes::GroupMerge,
+            &None::<()>,
+        )?
+    )?;
+
+    Ok( !links.is_empty() )
+}
+
+/// Walk every branch forward from the group ID (for groups without a revision index)
+fn walked_branches(group_id: &ActionHash) -> ExternResult<Vec<GroupBranch>> {
+    let mut branches = vec![];
+    let mut paths = vec![ vec![ group_id.to_owned() ] ];
+
+    while let Some(path) = paths.pop() {
+        let tip = path.last().unwrap().to_owned();
+        let updates = revision_updates( &tip )?;
+
+        if updates.is_empty() {
+            branches.push( GroupBranch {
+                head: tip.to_owned(),
+                timestamp: must_get( &tip )?.action().timestamp(),
+                revisions: path,
+            });
+            continue;
+        }
+
+        for update in updates {
+            let mut next_path = path.clone();
+            next_path.push( update );
+            paths.push( next_path );
+        }
+    }
+
+    Ok( branches )
+}
+
+/// Get every branch of a group's revision history
+///
+/// Branches are walked back from the tips of the group's revision index, so only the index links
+/// and the (immutable) revision actions are fetched.  Revisions made without an index link are
+/// only found when they are an ancestor of an indexed revision.
+fn branch_tree(group_id: &ActionHash) -> ExternResult<Vec<GroupBranch>> {
+    let indexed = indexed_revisions( group_id )?;
+
+    if indexed.is_empty() {
+        return walked_branches( group_id );
+    }
+
+    let mut histories = vec![];
+
+    for group_rev in indexed {
+        let mut history = trace_origin( &group_rev )?;
+        history.reverse();
+        histories.push( history );
+    }
+
+    let ancestors : HashSet<ActionHash> = histories.iter()
+        .flat_map(|history| history.iter().rev().skip(1).map(|(addr, _)| addr.to_owned() ) )
+        .collect();
+    let mut branches : Vec<GroupBranch> = vec![];
+
+    for history in histories {
+        let (head, action) = history.last().unwrap().to_owned();
+
+        if ancestors.contains( &head ) || branches.iter().any(|branch| branch.head == head ) {
+            continue;
+        }
+
+        branches.push( GroupBranch {
+            head,
+            timestamp: action.timestamp(),
+            revisions: history.into_iter().map(|(addr, _)| addr ).collect(),
+        });
+    }
+
+    Ok( branches )
+}
+
+/// Get every branch of a group's revision history (including merged branches)
+pub fn all_group_branches(group_id: &ActionHash) -> ExternResult<Vec<GroupBranch>> {
+    branch_tree( group_id )
+}
+
+/// Get the branches of a group's revision history that have not been merged
+pub fn group_branches(group_id: &ActionHash) -> ExternResult<Vec<GroupBranch>> {
+    let mut branches = vec![];
+
+    for branch in branch_tree( group_id )? {
+        if !is_merged_head( &branch.head )? {
+            branches.push( branch );
+        }
+    }
+
+    debug!("Found {} branches for group '{}'", branches.len(), group_id );
+    Ok( branches )
+}
+
+fn canonical_branch(group_id: &ActionHash) -> ExternResult<GroupBranch> {
+    let branches = group_branches( group_id )?;
+
+    Ok(
+        canonical_group_branch( &branches )
+            .ok_or(guest_error!(format!("Group has no revisions: {}", group_id )))?
+            .to_owned()
+    )
+}
+
+/// Get the revisions of a group's canonical branch in evolution order
+pub fn canonical_group_revisions(group_id: &ActionHash) -> ExternResult<Vec<ActionHash>> {
+    Ok( canonical_branch( group_id )?.revisions )
+}
+
+/// Get the head of a group's canonical branch
+pub fn latest_group_revision(group_id: &ActionHash) -> ExternResult<ActionHash> {
+    Ok( canonical_branch( group_id )?.head )
+}
+
+
+#[hdk_extern]
+pub fn get_group_forks(group_id: ActionHash) -> ExternResult<GroupForks> {
+    debug!("Get group forks: {}", group_id );
+    let branches = group_branches( &group_id )?;
+    let canonical = canonical_group_branch( &branches )
+        .ok_or(guest_error!(format!("Group has no revisions: {}", group_id )))?
+        .head.to_owned();
+
+    Ok( GroupForks {
+        group_id,
+        canonical,
+        branches,
+    })
+}
+
+
+#[hdk_extern]
+pub fn merge_group_branches(group_id: ActionHash) -> ExternResult<ActionHash> {
+    debug!("Merge group branches: {}", group_id );
+    let branches = group_branches( &group_id )?;
+
+    if branches.len() < 2 {
+        Err(guest_error!(format!("Group is not forked: {}", group_id )))?
+    }
+
+    let canonical = canonical_group_branch( &branches ).unwrap().to_owned();
+    let canonical_group : GroupEntry = must_get( &canonical.head )?.try_into()?;
+
+    if !canonical_group.is_admin( &agent_id()? ) {
+        Err(guest_error!(format!("Only admins of the group can merge branches")))?
+    }
+
+    let mut merged_group = canonical_group.to_owned();
+    let mut archive_links = GroupEntry::group_auth_archive_links( &canonical.head )?;
+
+    for branch in branches.iter().filter(|branch| branch.head != canonical.head ) {
+        let fork_point = canonical.fork_point( branch )
+            .ok_or(guest_error!(format!("Branch '{}' does not share a root with the canonical branch", branch.head )))?;
+        let fork_group : GroupEntry = must_get( &fork_point )?.try_into()?;
+        let branch_group : GroupEntry = must_get( &branch.head )?.try_into()?;
+        debug!("Merging branch '{}' (forked at '{}')", branch.head, fork_point );
+
+        merged_group = merge_group_entries( &fork_group, &merged_group, &branch_group )
+            .map_err(|message| guest_error!(message) )?;
+
+        // Archives made on the other branch must stay reachable from the merged revision
+        for link in GroupEntry::group_auth_archive_links( &branch.head )? {
+            if !archive_links.iter().any(|existing| existing.target == link.target ) {
+                archive_links.push( link );
+            }
+        }
+    }
+
+    merged_group.last_updated = sys_time()?.as_millis() as u64;
+
+    let contributors_diff = canonical_group.contributors_diff( &merged_group );
+    let action_hash = update_entry( canonical.head.to_owned(), merged_group.to_input() )?;
+
+    let windows = contribution_windows( &canonical_group, &group_resignations( &canonical.head )? );
+
+    create_group_revision_links( &group_id, &action_hash, contributors_diff, archive_links, &windows )?;
+
+    // Merged heads are no longer branches of the group
+    for branch in branches.iter().filter(|branch| branch.head != canonical.head ) {
+        create_link( branch.head.to_owned(), action_hash.to_owned(), LinkTypes::GroupMerge, () )?;
+    }
+
+    update_group_key( &group_id, &action_hash )?;
+
+    Ok( action_hash )
+}
@@ -0,0 +1,405 @@
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+    exists,
+};
+use crate::hdi_extensions::{
+    trace_origin_root,
+    ScopedTypeConnector,
+    // Macros
+    guest_error,
+};
+use crate::scoped_types::entry_traits::*;
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    // Entry Structs
+    GroupEntry,
+    ContributionsAnchorEntry,
+    ArchivedContributionsAnchorEntry,
+    ContributionAnchors,
+    MembershipWindow,
+
+    // Link Tags
+    ContributionUpdateTag,
+    governed_payload,
+
+    // Output Structs
+    GroupGraphLinkType,
+    GroupIntegrityIssue,
+    GroupRevisionReport,
+    GroupRevisionRepair,
+};
+use crate::create_if_not_exists;
+use crate::group_forks::all_group_branches;
+use crate::key_succession::key_successors;
+use crate::group_resignations::group_resignations;
+use crate::contribution_windows::contribution_windows;
+use crate::content_tombstones::{
+    archived_tombstone_tag,
+    is_tombstone_copy,
+};
+use crate::content_ordering::{
+    archived_position_tag,
+    is_position_copy,
+};
+
+
+
+/// The link set that `create_group`/`update_group` are expected to produce for a group revision
+pub struct ExpectedRevisionLinks {
+    pub group_id: ActionHash,
+    pub timestamp: Timestamp,
+    /// The contributors of this revision paired with their contributions anchor
+    pub contributors: Vec<(AgentPubKey, ContributionsAnchorEntry)>,
+    /// The contributors removed by this revision (and their key successors) paired with their
+    /// archive anchor and resignation (if they resigned)
+    pub removed: Vec<(AgentPubKey, ArchivedContributionsAnchorEntry, Option<MembershipWindow>)>,
+    /// The archive links of the previous revision (which must be carried forward)
+    pub carried_archives: Vec<Link>,
+}
+
+impl ExpectedRevisionLinks {
+    pub fn load(group_rev: &ActionHash) -> ExternResult<Self> {
+        let group_id = trace_origin_root( group_rev )?.0;
+        let record = must_get( group_rev )?;
+        let group = GroupEntry::try_from_record( &record )?;
+
+        let contributors = group.contributors().into_iter()
+            .map(|pubkey| (
+                pubkey.to_owned(),
+                ContributionsAnchorEntry( group_id.to_owned(), pubkey )
+            ))
+            .collect();
+
+        let (removed, carried_archives) = match record.action() {
+            Action::Update(update) => {
+                let prev_rev = &update.original_action_address;
+                let prev_group : GroupEntry = must_get( prev_rev )?.try_into()?;
+                let windows = contribution_windows( &prev_group, &group_resignations( prev_rev )? );
+                let mut removed = vec![];
+
+                // A removed contributor's key successors are archived along with them
+                for pubkey in prev_group.contributors_diff( &group ).removed {
+                    let window = windows.get( &pubkey ).cloned();
+                    let successors = key_successors( &group_id, &pubkey )?;
+
+                    for pubkey in std::iter::once( pubkey ).chain( successors ) {
+                        removed.push((
+                            pubkey.to_owned(),
+                            ArchivedContributionsAnchorEntry::new( group_rev.to_owned(), pubkey ),
+                            window.to_owned(),
+                        ));
+                    }
+                }
+
+                ( removed, GroupEntry::group_auth_archive_links( prev_rev )? )
+            },
+            _ => ( vec![], vec![] ),
+        };
+
+        Ok( ExpectedRevisionLinks {
+            group_id,
+            timestamp: record.action().timestamp(),
+            contributors,
+            removed,
+            carried_archives,
+        })
+    }
+
+    /// Get the active anchor links of a removed contributor that are missing from their archive
+    ///
+    /// Only links that existed when the group revision was made (and within the contributor's
+    /// window) are expected in the archive.
+    pub fn missing_archive_copies(
+        &self,
+        agent: &AgentPubKey,
+        archive_anchor: &ArchivedContributionsAnchorEntry,
+        window: Option<&MembershipWindow>,
+    ) -> ExternResult<Vec<(LinkTypes, Link)>> {
+        let active_anchor = ContributionsAnchorEntry( self.group_id.to_owned(), agent.to_owned() );
+        let archived_creates = archive_anchor.create_links()?;
+        let archived_updates = archive_anchor.update_links()?;
+        let archived_tombstones = archive_anchor.tombstone_links()?;
+        let archived_positions = archive_anchor.position_links()?;
+
+        let is_copied = |archived: &Vec<Link>, link: &Link| {
+            archived.iter().any(|copy| copy.target == link.target && copy.tag == link.tag )
+        };
+        let is_expected = |link: &Link| {
+            link.timestamp <= self.timestamp
+                && window.is_none_or(|window| window.contains( &link.timestamp ) )
+        };
+
+        let mut missing = vec![];
+
+        for link in active_anchor.create_links()? {
+            if is_expected( &link ) && !is_copied( &archived_creates, &link ) {
+                missing.push( (LinkTypes::Contribution, link) );
+            }
+        }
+
+        for link in active_anchor.update_links()? {
+            if is_expected( &link ) && !is_copied( &archived_updates, &link ) {
+                missing.push( (LinkTypes::ContributionUpdate, link) );
+            }
+        }
+
+        for link in active_anchor.tombstone_links()? {
+            let is_copied = archived_tombstones.iter().any(|copy| is_tombstone_copy( copy, &link ) );
+
+            if is_expected( &link ) && !is_copied {
+                missing.push( (LinkTypes::ContentTombstone, link) );
+            }
+        }
+
+        for link in active_anchor.position_links()? {
+            let is_copied = archived_positions.iter().any(|copy| is_position_copy( copy, &link ) );
+
+            if is_expected( &link ) && !is_copied {
+                missing.push( (LinkTypes::ContentPosition, link) );
+            }
+        }
+
+        Ok( missing )
+    }
+}
+
+
+fn graph_link_type(link_type: &LinkTypes) -> GroupGraphLinkType {
+    match link_type {
+        LinkTypes::GroupAuth => GroupGraphLinkType::GroupAuth,
+        LinkTypes::GroupAuthArchive => GroupGraphLinkType::GroupAuthArchive,
+        LinkTypes::ContributionUpdate => GroupGraphLinkType::ContributionUpdate,
+        LinkTypes::ContentTombstone => GroupGraphLinkType::ContentTombstone,
+        LinkTypes::ContentPosition => GroupGraphLinkType::ContentPosition,
+        _ => GroupGraphLinkType::Contribution,
+    }
+}
+
+fn has_target(links: &[Link], target: &EntryHash) -> bool {
+    let target : AnyLinkableHash = target.to_owned().into();
+    links.iter().any(|link| link.target == target )
+}
+
+fn target_exists(target: &AnyLinkableHash) -> ExternResult<bool> {
+    Ok(
+        match target.to_owned().into_any_dht_hash() {
+            Some(addr) => get( addr, GetOptions::default() )?.is_some(),
+            // External hashes cannot be resolved on the DHT
+            None => true,
+        }
+    )
+}
+
+
+/// Check a single group revision against the link set it is expected to have
+pub fn check_group_revision(group_rev: &ActionHash) -> ExternResult<GroupRevisionReport> {
+    let expected = ExpectedRevisionLinks::load( group_rev )?;
+    let auth_links = GroupEntry::group_auth_links( group_rev )?;
+    let archive_links = GroupEntry::group_auth_archive_links( group_rev )?;
+    let mut issues = vec![];
+
+    for (agent, anchor) in expected.contributors.iter() {
+        let anchor_hash = hash_entry( anchor )?;
+
+        if !has_target( &auth_links, &anchor_hash ) {
+            issues.push( GroupIntegrityIssue::MissingGroupAuth {
+                agent: agent.to_owned(),
+                anchor: anchor_hash,
+            });
+        }
+    }
+
+    for link in expected.carried_archives.iter() {
+        if let Some(anchor_hash) = link.target.to_owned().into_entry_hash() {
+            if !has_target( &archive_links, &anchor_hash ) {
+                issues.push( GroupIntegrityIssue::MissingCarriedArchive {
+                    anchor: anchor_hash,
+                });
+            }
+        }
+    }
+
+    for (agent, archive_anchor, window) in expected.removed.iter() {
+        let archive_anchor_hash = hash_entry( archive_anchor )?;
+
+        if !exists( &archive_anchor_hash )? {
+            issues.push( GroupIntegrityIssue::MissingArchiveAnchor {
+                agent: agent.to_owned(),
+                anchor: archive_anchor_hash.to_owned(),
+            });
+        }
+
+        if !has_target( &archive_links, &archive_anchor_hash ) {
+            issues.push( GroupIntegrityIssue::MissingGroupAuthArchive {
+                agent: agent.to_owned(),
+                anchor: archive_anchor_hash.to_owned(),
+            });
+        }
+
+        for (link_type, link) in expected.missing_archive_copies( agent, archive_anchor, window.as_ref() )? {
+            issues.push( GroupIntegrityIssue::MissingArchivedLink {
+                agent: agent.to_owned(),
+                link_type: graph_link_type( &link_type ),
+                create_link_hash: link.create_link_hash,
+                target: link.target,
+            });
+        }
+    }
+
+    let anchor_links = auth_links.iter().map(|link| (LinkTypes::GroupAuth, link) )
+        .chain( archive_links.iter().map(|link| (LinkTypes::GroupAuthArchive, link) ) );
+
+    for (link_type, link) in anchor_links {
+        let anchor_hash = match link.target.to_owned().into_entry_hash() {
+            Some(hash) => hash,
+            None => continue,
+        };
+
+        if !exists( &anchor_hash )? {
+            issues.push( GroupIntegrityIssue::DanglingTarget {
+                link_type: graph_link_type( &link_type ),
+                create_link_hash: link.create_link_hash.to_owned(),
+                target: link.target.to_owned(),
+            });
+            continue;
+        }
+
+        let anchor : ContributionAnchors = must_get( &anchor_hash )?.try_into()?;
+        let (create_links, update_links) = match &anchor {
+            ContributionAnchors::Active(anchor) => ( anchor.create_links()?, anchor.update_links()? ),
+            ContributionAnchors::Archive(anchor) => ( anchor.create_links()?, anchor.update_links()? ),
+        };
+
+        for update_link in update_links.iter() {
+            if let Err(reason) = ContributionUpdateTag::from_bytes( governed_payload( &update_link.tag.0 ) ) {
+                issues.push( GroupIntegrityIssue::MalformedUpdateTag {
+                    anchor: anchor_hash.to_owned(),
+                    create_link_hash: update_link.create_link_hash.to_owned(),
+                    tag: update_link.tag.to_owned(),
+                    reason,
+                });
+            }
+        }
+
+        let content_links = create_links.into_iter().map(|link| (LinkTypes::Contribution, link) )
+            .chain( update_links.into_iter().map(|link| (LinkTypes::ContributionUpdate, link) ) );
+
+        for (link_type, link) in content_links {
+            if !target_exists( &link.target )? {
+                issues.push( GroupIntegrityIssue::DanglingTarget {
+                    link_type: graph_link_type( &link_type ),
+                    create_link_hash: link.create_link_hash,
+                    target: link.target,
+                });
+            }
+        }
+    }
+
+    debug!("Found {} issues for group revision '{}'", issues.len(), group_rev );
+    Ok( GroupRevisionReport {
+        group_rev: group_rev.to_owned(),
+        issues,
+    })
+}
+
+
+#[hdk_extern]
+pub fn check_group_integrity(group_id: ActionHash) -> ExternResult<Vec<GroupRevisionReport>> {
+    debug!("Check group integrity: {}", group_id );
+    let mut group_revs : Vec<ActionHash> = vec![];
+
+    // Every branch is checked so that forked revisions are not missed
+    for branch in all_group_branches( &group_id )? {
+        for group_rev in branch.revisions {
+            if !group_revs.contains( &group_rev ) {
+                group_revs.push( group_rev );
+            }
+        }
+    }
+
+    group_revs.iter()
+        .map( check_group_revision )
+        .collect()
+}
+
+
+#[hdk_extern]
+pub fn repair_group_revision(group_rev: ActionHash) -> ExternResult<GroupRevisionRepair> {
+    debug!("Repair group revision: {}", group_rev );
+    let group : GroupEntry = must_get( &group_rev )?.try_into()?;
+
+    if !group.is_admin( &agent_id()? ) {
+        Err(guest_error!(format!("Only admins of the group revision can repair it")))?
+    }
+
+    let expected = ExpectedRevisionLinks::load( &group_rev )?;
+    let auth_links = GroupEntry::group_auth_links( &group_rev )?;
+    let archive_links = GroupEntry::group_auth_archive_links( &group_rev )?;
+    let mut repair = GroupRevisionRepair {
+        group_rev: group_rev.to_owned(),
+        created_anchors: vec![],
+        created_links: vec![],
+    };
+
+    for (agent, anchor) in expected.contributors.iter() {
+        let anchor_hash = hash_entry( anchor )?;
+
+        if let Some(action_hash) = create_if_not_exists( anchor )? {
+            debug!("Created missing contributions anchor for agent: {}", agent );
+            repair.created_anchors.push( action_hash );
+        }
+
+        if !has_target( &auth_links, &anchor_hash ) {
+            debug!("Creating missing group auth link for agent: {}", agent );
+            repair.created_links.push(
+                create_link( group_rev.to_owned(), anchor_hash, LinkTypes::GroupAuth, () )?
+            );
+        }
+    }
+
+    for link in expected.carried_archives.iter() {
+        if let Some(anchor_hash) = link.target.to_owned().into_entry_hash() {
+            if !has_target( &archive_links, &anchor_hash ) {
+                debug!("Carrying forward missing archive link: {}", anchor_hash );
+                repair.created_links.push(
+                    create_link( group_rev.to_owned(), anchor_hash, LinkTypes::GroupAuthArchive, link.tag.to_owned() )?
+                );
+            }
+        }
+    }
+
+    for (agent, archive_anchor, window) in expected.removed.iter() {
+        let archive_anchor_hash = hash_entry( archive_anchor )?;
+
+        if let Some(action_hash) = create_if_not_exists( archive_anchor )? {
+            debug!("Created missing archive anchor for agent: {}", agent );
+            repair.created_anchors.push( action_hash );
+        }
+
+        if !has_target( &archive_links, &archive_anchor_hash ) {
+            debug!("Creating missing group auth archive link for agent: {}", agent );
+            repair.created_links.push(
+                create_link( group_rev.to_owned(), archive_anchor_hash.to_owned(), LinkTypes::GroupAuthArchive, () )?
+            );
+        }
+
+        let missing = expected.missing_archive_copies( agent, archive_anchor, window.as_ref() )?;
+        debug!("Copying {} missing links for auth archive: {}", missing.len(), agent );
+        for (link_type, link) in missing {
+            let tag = match link_type {
+                LinkTypes::ContentTombstone => archived_tombstone_tag( &link )?,
+                LinkTypes::ContentPosition => archived_position_tag( &link )?,
+                _ => link.tag,
+            };
+            repair.created_links.push(
+                create_link( archive_anchor_hash.to_owned(), link.target, link_type, tag )?
+            );
+        }
+    }
+
+    Ok( repair )
+}
@@ -0,0 +1,370 @@
+use crate::hdk::prelude::*;
+use crate::hdk::prelude::nonce::XSalsa20Poly1305Nonce;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use crate::hdi_extensions::{
+    ScopedTypeConnector,
+    // Macros
+    guest_error,
+};
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    create_link_input,
+    X25519_PUB_KEY_LENGTH,
+    XSALSA20_POLY1305_NONCE_LENGTH,
+
+    // Entry Structs
+    GroupEntry,
+    GroupKeyEntry,
+    GroupKeyShare,
+
+    // Input Structs
+    EncryptGroupContentInput,
+
+    // Output Structs
+    GroupCiphertext,
+    GroupKeyEpoch,
+};
+use crate::group_forks::{
+    latest_group_revision,
+    canonical_group_revisions,
+};
+
+
+
+fn x25519_key(bytes: &[u8]) -> ExternResult<X25519PubKey> {
+    let key : [u8; X25519_PUB_KEY_LENGTH] = bytes.try_into()
+        .map_err(|_| guest_error!(format!("X25519 public key must be {} bytes; not {}", X25519_PUB_KEY_LENGTH, bytes.len() )) )?;
+
+    Ok( X25519PubKey::from( key ) )
+}
+
+fn encrypted_data(nonce: &[u8], data: &[u8]) -> ExternResult<XSalsa20Poly1305EncryptedData> {
+    let nonce : [u8; XSALSA20_POLY1305_NONCE_LENGTH] = nonce.try_into()
+        .map_err(|_| guest_error!(format!("Nonce must be {} bytes; not {}", XSALSA20_POLY1305_NONCE_LENGTH, nonce.len() )) )?;
+
+    Ok( XSalsa20Poly1305EncryptedData::new( XSalsa20Poly1305Nonce::from( nonce ), data.to_vec() ) )
+}
+
+/// Get an agent's latest registered X25519 public key
+pub fn agent_encryption_key(agent: &AgentPubKey) -> ExternResult<Option<X25519PubKey>> {
+    let mut links = get_links(
+        create_link_input(
+            agent,
+            &LinkTypes::EncryptionKey,
+            &None::<()>,
+        )?
+    )?;
+    links.sort_by_key(|link| link.timestamp );
+
+    match links.last() {
+        Some(link) => Ok( Some( x25519_key( &link.tag.0 )? ) ),
+        None => Ok( None ),
+    }
+}
+
+/// Get every group key linked to a group
+fn group_keys(group_id: &ActionHash) -> ExternResult<Vec<(ActionHash, GroupKeyEntry)>> {
+    let links = get_links(
+        create_link_input(
+            group_id,
+            &LinkTypes::GroupKey,
+            &None::<()>,
+        )?
+    )?;
+    let mut keys = vec![];
+
+    for link in links {
+        let key_addr = match link.target.into_action_hash() {
+            Some(addr) => addr,
+            None => {
+                debug!("WARNING: Should be unreachable because LinkTypes::GroupKey validation only allows group key targets");
+                continue;
+            },
+        };
+        let group_key = GroupKeyEntry::try_from_record( &must_get( &key_addr )? )?;
+
+        keys.push( ( key_addr, group_key ) );
+    }
+
+    Ok( keys )
+}
+
+/// A group revision paired with the group key (address and entry) that applies to it
+type GroupKeyEpochs = Vec<(ActionHash, Option<(ActionHash, GroupKeyEntry)>)>;
+
+/// Get the group key that applies to each canonical group revision
+///
+/// A revision uses the highest epoch key made for it, or else the key of the revision before it.
+/// A key that has been re-shared (is the `previous` of another key) is superseded.
+pub fn group_key_epochs(group_id: &ActionHash) -> ExternResult<GroupKeyEpochs> {
+    let keys = group_keys( group_id )?;
+    let mut current : Option<(ActionHash, GroupKeyEntry)> = None;
+    let mut epochs = vec![];
+
+    for group_rev in canonical_group_revisions( group_id )? {
+        let is_superseded = |addr: &ActionHash| keys.iter()
+            .any(|(_, group_key)| group_key.previous.as_ref() == Some( addr ) );
+        let revision_key = keys.iter()
+            .filter(|(_, group_key)| group_key.group_rev == group_rev )
+            .max_by(|(a_addr, a), (b_addr, b)| {
+                a.epoch.cmp( &b.epoch )
+                    .then( is_superseded( b_addr ).cmp( &is_superseded( a_addr ) ) )
+                    .then( a_addr.get_raw_39().cmp( b_addr.get_raw_39() ) )
+            });
+
+        if let Some(revision_key) = revision_key {
+            current = Some( revision_key.to_owned() );
+        }
+
+        epochs.push( ( group_rev, current.to_owned() ) );
+    }
+
+    Ok( epochs )
+}
+
+/// Get the group key of the latest group revision
+pub fn latest_group_key(group_id: &ActionHash) -> ExternResult<Option<(ActionHash, GroupKeyEntry)>> {
+    Ok( group_key_epochs( group_id )?.pop().and_then(|(_, group_key)| group_key ) )
+}
+
+/// Ingest the caller's share of a group key into their keystore
+fn group_key_ref(group_key: &GroupKeyEntry) -> ExternResult<XSalsa20Poly1305KeyRef> {
+    let agent = agent_id()?;
+    let share = group_key.share_for( &agent )
+        .ok_or(guest_error!(format!("Agent ({}) does not have a share of group key epoch {}", agent, group_key.epoch )))?;
+
+    x_salsa20_poly1305_shared_secret_ingest(
+        x25519_key( &share.recipient_key )?,
+        x25519_key( &group_key.sender_key )?,
+        encrypted_data( &share.nonce, &share.encrypted_key )?,
+        None,
+    )
+}
+
+/// Get the contributors of a group revision that do not have a share of the given group key
+fn pending_recipients(group: &GroupEntry, group_key: &GroupKeyEntry) -> Vec<AgentPubKey> {
+    let recipients = group_key.recipients();
+
+    group.contributors().into_iter()
+        .filter(|agent| !recipients.contains( agent ) )
+        .collect()
+}
+
+/// Export a key to every contributor of a group revision and record it as a group key
+///
+/// Contributors that have not registered an encryption key are skipped and remain pending.
+fn share_group_key(
+    group_id: &ActionHash,
+    group_rev: &ActionHash,
+    epoch: u32,
+    key_ref: XSalsa20Poly1305KeyRef,
+    previous: Option<ActionHash>,
+) -> ExternResult<ActionHash> {
+    let group = GroupEntry::try_from_record( &must_get( group_rev )? )?;
+    let sender_key = register_encryption_key(())?;
+    let mut shares : Vec<GroupKeyShare> = vec![];
+
+    for agent in group.contributors() {
+        if shares.iter().any(|share| share.agent == agent ) {
+            continue;
+        }
+
+        let recipient_key = match agent_encryption_key( &agent )? {
+            Some(key) => key,
+            None => {
+                debug!("Contributor ({}) is pending because they have not registered an encryption key", agent );
+                continue;
+            },
+        };
+        let encrypted_key = x_salsa20_poly1305_shared_secret_export(
+            sender_key.to_owned(),
+            recipient_key.to_owned(),
+            key_ref.to_owned(),
+        )?;
+
+        shares.push( GroupKeyShare {
+            agent,
+            recipient_key: recipient_key.as_ref().to_vec(),
+            nonce: encrypted_key.as_nonce_ref().as_ref().to_vec(),
+            encrypted_key: encrypted_key.as_encrypted_data_ref().to_vec(),
+        });
+    }
+
+    let group_key = GroupKeyEntry {
+        group_id: group_id.to_owned(),
+        group_rev: group_rev.to_owned(),
+        epoch,
+        sender_key: sender_key.as_ref().to_vec(),
+        shares,
+        previous,
+    };
+    debug!("Sharing group key epoch {} with {} contributors", group_key.epoch, group_key.shares.len() );
+
+    let action_hash = create_entry( group_key.to_input() )?;
+    create_link( group_id.to_owned(), action_hash.to_owned(), LinkTypes::GroupKey, () )?;
+
+    Ok( action_hash )
+}
+
+/// Share the group key with the contributors of a new group revision (if the group is encrypted)
+///
+/// The key is rotated when someone who had the current key is no longer a contributor; otherwise
+/// the current key is re-shared when a contributor without a share has registered an encryption
+/// key.  Re-sharing is skipped when the caller does not have a share of the current key, so that
+/// another admin can share it later (see [`share_group_key_with_pending`]).
+pub(crate) fn update_group_key(group_id: &ActionHash, group_rev: &ActionHash) -> ExternResult<Option<ActionHash>> {
+    let (previous_addr, previous) = match latest_group_key( group_id )? {
+        Some(group_key) => group_key,
+        None => return Ok( None ),
+    };
+    let group = GroupEntry::try_from_record( &must_get( group_rev )? )?;
+    let recipients = previous.recipients();
+
+    if recipients.iter().any(|agent| !group.is_contributor( agent ) ) {
+        debug!("Rotating group key to epoch {}", previous.epoch + 1 );
+        let key_ref = x_salsa20_poly1305_shared_secret_create_random( None )?;
+
+        return Ok( Some( share_group_key( group_id, group_rev, previous.epoch + 1, key_ref, Some( previous_addr ) )? ) );
+    }
+
+    let mut shareable = false;
+
+    for agent in pending_recipients( &group, &previous ) {
+        if agent_encryption_key( &agent )?.is_some() {
+            shareable = true;
+            break;
+        }
+    }
+
+    if shareable && previous.share_for( &agent_id()? ).is_none() {
+        debug!("Skipping group key re-share because the caller does not have a share of epoch {}", previous.epoch );
+        return Ok( None );
+    }
+
+    if shareable {
+        let key_ref = group_key_ref( &previous )?;
+
+        return Ok( Some( share_group_key( group_id, group_rev, previous.epoch, key_ref, Some( previous_addr ) )? ) );
+    }
+
+    Ok( None )
+}
+
+
+/// Publish an X25519 public key for the caller (if they do not have one yet)
+#[hdk_extern]
+pub fn register_encryption_key(_: ()) -> ExternResult<X25519PubKey> {
+    let agent = agent_id()?;
+
+    if let Some(key) = agent_encryption_key( &agent )? {
+        return Ok( key );
+    }
+
+    let key = create_x25519_keypair()?;
+    create_link( agent.to_owned(), agent, LinkTypes::EncryptionKey, key.as_ref().to_vec() )?;
+
+    Ok( key )
+}
+
+#[hdk_extern]
+pub fn get_agent_encryption_key(agent: AgentPubKey) -> ExternResult<Option<X25519PubKey>> {
+    agent_encryption_key( &agent )
+}
+
+/// Make a group private by sharing a new group key (epoch 0) with its contributors
+///
+/// Contributors that have not called [`register_encryption_key`] are pending until the key is
+/// re-shared (see [`share_group_key_with_pending`]).
+#[hdk_extern]
+pub fn create_group_key(group_id: ActionHash) -> ExternResult<ActionHash> {
+    let group_rev = latest_group_revision( &group_id )?;
+    let group = GroupEntry::try_from_record( &must_get( &group_rev )? )?;
+
+    if !group.is_admin( &agent_id()? ) {
+        Err(guest_error!(format!("Only admins of the group can create a group key")))?
+    }
+
+    if !group_keys( &group_id )?.is_empty() {
+        Err(guest_error!(format!("Group ({}) already has a group key", group_id )))?
+    }
+
+    let key_ref = x_salsa20_poly1305_shared_secret_create_random( None )?;
+
+    share_group_key( &group_id, &group_rev, 0, key_ref, None )
+}
+
+/// Share the current group key with pending contributors that have since registered an encryption
+/// key
+#[hdk_extern]
+pub fn share_group_key_with_pending(group_id: ActionHash) -> ExternResult<Option<ActionHash>> {
+    let group_rev = latest_group_revision( &group_id )?;
+    let group = GroupEntry::try_from_record( &must_get( &group_rev )? )?;
+
+    if !group.is_admin( &agent_id()? ) {
+        Err(guest_error!(format!("Only admins of the group can share the group key")))?
+    }
+
+    update_group_key( &group_id, &group_rev )
+}
+
+#[hdk_extern]
+pub fn get_group_key_epochs(group_id: ActionHash) -> ExternResult<Vec<GroupKeyEpoch>> {
+    let mut epochs = vec![];
+
+    for (group_rev, group_key) in group_key_epochs( &group_id )? {
+        let pending = match &group_key {
+            Some((_, group_key)) => {
+                let group = GroupEntry::try_from_record( &must_get( &group_rev )? )?;
+                pending_recipients( &group, group_key )
+            },
+            None => vec![],
+        };
+
+        epochs.push( GroupKeyEpoch {
+            epoch: group_key.as_ref().map(|(_, group_key)| group_key.epoch ),
+            group_key: group_key.map(|(key_addr, _)| key_addr ),
+            group_rev,
+            pending,
+        });
+    }
+
+    Ok( epochs )
+}
+
+#[hdk_extern]
+pub fn encrypt_group_content(input: EncryptGroupContentInput) -> ExternResult<GroupCiphertext> {
+    let (key_addr, group_key) = latest_group_key( &input.group_id )?
+        .ok_or(guest_error!(format!("Group ({}) does not have a group key", input.group_id )))?;
+    let key_ref = group_key_ref( &group_key )?;
+    let encrypted = x_salsa20_poly1305_encrypt( key_ref, input.data.into() )?;
+
+    Ok( GroupCiphertext {
+        group_id: input.group_id,
+        group_key: key_addr,
+        epoch: group_key.epoch,
+        nonce: encrypted.as_nonce_ref().as_ref().to_vec(),
+        data: encrypted.as_encrypted_data_ref().to_vec(),
+    })
+}
+
+#[hdk_extern]
+pub fn decrypt_group_content(ciphertext: GroupCiphertext) -> ExternResult<Vec<u8>> {
+    let group_key = GroupKeyEntry::try_from_record( &must_get( &ciphertext.group_key )? )?;
+
+    if group_key.group_id != ciphertext.group_id {
+        Err(guest_error!(format!("Group key ({}) does not belong to group ({})", ciphertext.group_key, ciphertext.group_id )))?
+    }
+
+    let key_ref = group_key_ref( &group_key )?;
+    let data = x_salsa20_poly1305_decrypt(
+        key_ref,
+        encrypted_data( &ciphertext.nonce, &ciphertext.data )?,
+    )?.ok_or(guest_error!(format!("Failed to decrypt group content with group key epoch {}", group_key.epoch )))?;
+
+    Ok( data.as_ref().to_vec() )
+}
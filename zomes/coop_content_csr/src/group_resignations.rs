@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use crate::hdi_extensions::{
+    trace_origin,
+    ScopedTypeConnector,
+    // Macros
+    guest_error,
+};
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    create_link_input,
+
+    // Entry Structs
+    GroupEntry,
+};
+use crate::group_forks::latest_group_revision;
+
+
+
+fn revision_resignations(group_rev: &ActionHash) -> ExternResult<Vec<(AgentPubKey, Timestamp)>> {
+    let links = get_links(
+        create_link_input(
+            group_rev,
+            &LinkTypes::GroupResignation,
+            &None::<()>,
+        )?
+    )?;
+
+    Ok(
+        links.into_iter()
+            .filter_map(|link| {
+                let resigned_at = link.timestamp;
+                match link.target.into_agent_pub_key() {
+                    Some(agent) => Some( (agent, resigned_at) ),
+                    None => {
+                        debug!("WARNING: Should be unreachable because LinkTypes::GroupResignation validation only allows AgentPubKey target");
+                        None
+                    },
+                }
+            })
+            .collect()
+    )
+}
+
+/// Get the members of a group revision that have resigned and when they resigned
+///
+/// Resignations are collected from the given revision and each revision before it, back to the
+/// revision where the member was last added.  A resignation made against an earlier revision is
+/// therefore kept until an admin update formalises it.  If a member resigned more than once, the
+/// earliest resignation is used.
+pub fn group_resignations(group_rev: &ActionHash) -> ExternResult<HashMap<AgentPubKey, Timestamp>> {
+    let mut resignations : HashMap<AgentPubKey, Timestamp> = HashMap::new();
+    let mut members : Option<Vec<AgentPubKey>> = None;
+
+    for (rev_addr, _) in trace_origin( group_rev )? {
+        let group : GroupEntry = must_get( &rev_addr )?.try_into()?;
+
+        // Only the members that have been in every revision since the given one are followed
+        let current = members.get_or_insert_with(|| group.members.to_owned() );
+        current.retain(|member| group.members.contains( member ) );
+
+        if current.is_empty() {
+            break;
+        }
+
+        for (agent, resigned_at) in revision_resignations( &rev_addr )? {
+            if !current.contains( &agent ) {
+                continue;
+            }
+
+            resignations.entry( agent )
+                .and_modify(|timestamp| *timestamp = std::cmp::min( *timestamp, resigned_at ) )
+                .or_insert( resigned_at );
+        }
+    }
+
+    Ok( resignations )
+}
+
+
+#[hdk_extern]
+pub fn leave_group(group_id: ActionHash) -> ExternResult<ActionHash> {
+    let agent = agent_id()?;
+    let group_rev = latest_group_revision( &group_id )?;
+    let group = GroupEntry::try_from_record( &must_get( &group_rev )? )?;
+    debug!("Agent ({}) leaving group revision: {}", agent, group_rev );
+
+    if !group.is_member( &agent ) {
+        Err(guest_error!(format!("Only members of the group can leave; admins must update the group")))?
+    }
+
+    create_link( group_rev, agent, LinkTypes::GroupResignation, () )
+}
+
+#[hdk_extern]
+pub fn get_group_resignations(group_id: ActionHash) -> ExternResult<Vec<(AgentPubKey, Timestamp)>> {
+    let group_rev = latest_group_revision( &group_id )?;
+
+    Ok( group_resignations( &group_rev )?.into_iter().collect() )
+}
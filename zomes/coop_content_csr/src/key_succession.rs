@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+use crate::hdk::prelude::*;
+use crate::hdk_extensions::{
+    agent_id,
+    must_get,
+};
+use crate::hdi_extensions::{
+    ScopedTypeConnector,
+};
+use coop_content::{
+    LinkTypes,
+};
+use coop_content_sdk::{
+    create_link_input,
+    KEY_SUCCESSION_MAX_DEPTH,
+
+    // Entry Structs
+    ContributionsAnchorEntry,
+    KeySuccessionEntry,
+
+    // Input Structs
+    RecordKeySuccessionInput,
+    GroupAgentInput,
+};
+use crate::create_if_not_exists;
+use crate::group_forks::latest_group_revision;
+
+
+
+/// Get the keys that succeed an agent in a group (in succession order)
+pub fn key_successors(group_id: &ActionHash, agent: &AgentPubKey) -> ExternResult<Vec<AgentPubKey>> {
+    let mut successors = vec![];
+    let mut seen = HashSet::from([ agent.to_owned() ]);
+    let mut current = vec![ agent.to_owned() ];
+
+    for _ in 0..KEY_SUCCESSION_MAX_DEPTH {
+        let mut next = vec![];
+
+        for agent in current {
+            let anchor_hash = hash_entry( ContributionsAnchorEntry( group_id.to_owned(), agent ) )?;
+            let links = get_links(
+                create_link_input(
+                    &anchor_hash,
+                    &LinkTypes::KeySuccession,
+                    &None::<()>,
+                )?
+            )?;
+
+            for link in links {
+                let succession_addr = match link.target.into_action_hash() {
+                    Some(addr) => addr,
+                    None => continue,
+                };
+                let succession = KeySuccessionEntry::try_from_record( &must_get( &succession_addr )? )?;
+
+                if seen.insert( succession.successor.to_owned() ) {
+                    successors.push( succession.successor.to_owned() );
+                    next.push( succession.successor );
+                }
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+
+        current = next;
+    }
+
+    Ok( successors )
+}
+
+/// Get an agent's anchor followed by the anchors of its key successors
+pub fn merged_key_anchors(anchor: ContributionsAnchorEntry) -> ExternResult<Vec<ContributionsAnchorEntry>> {
+    let successors = key_successors( anchor.group(), anchor.author() )?;
+    let group_id = anchor.group().to_owned();
+    let mut anchors = vec![ anchor ];
+
+    for successor in successors {
+        anchors.push( ContributionsAnchorEntry( group_id.to_owned(), successor ) );
+    }
+
+    Ok( anchors )
+}
+
+/// Extend a list of authorities with their key successors
+pub fn with_key_successors(group_id: &ActionHash, agents: Vec<AgentPubKey>) -> ExternResult<Vec<AgentPubKey>> {
+    let mut authorities = agents.clone();
+
+    for agent in agents {
+        for successor in key_successors( group_id, &agent )? {
+            if !authorities.contains( &successor ) {
+                authorities.push( successor );
+            }
+        }
+    }
+
+    Ok( authorities )
+}
+
+
+#[hdk_extern]
+pub fn record_key_succession(input: RecordKeySuccessionInput) -> ExternResult<ActionHash> {
+    let agent = agent_id()?;
+    let succession = KeySuccessionEntry {
+        group_id: input.group_id.to_owned(),
+        group_rev: latest_group_revision( &input.group_id )?,
+        predecessor: agent.to_owned(),
+        successor: input.successor,
+        previous: input.previous,
+    };
+    debug!("Recording key succession: {:#?}", succession );
+
+    let action_hash = create_entry( succession.to_input() )?;
+    let anchor = ContributionsAnchorEntry( input.group_id, agent );
+    let anchor_hash = hash_entry( &anchor )?;
+
+    create_if_not_exists( &anchor )?;
+    create_link( anchor_hash, action_hash.to_owned(), LinkTypes::KeySuccession, () )?;
+
+    Ok( action_hash )
+}
+
+#[hdk_extern]
+pub fn get_key_successors(input: GroupAgentInput) -> ExternResult<Vec<AgentPubKey>> {
+    key_successors( &input.group_id, &input.agent )
+}
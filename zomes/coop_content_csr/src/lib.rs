@@ -1,11 +1,30 @@
 mod scoped_types;
+mod group_integrity;
+mod group_forks;
+mod content_ordering;
+mod content_tombstones;
+mod content_history;
+mod contributor_stats;
+mod group_resignations;
+mod key_succession;
+mod contribution_windows;
+mod group_changes;
+mod content_groups;
+mod group_directory;
+mod group_keys;
+mod group_capabilities;
+mod content_proposals;
 
 pub use coop_content::hdi;
 pub use coop_content::hdi_extensions;
 pub use coop_content_sdk::hdk;
 pub use coop_content_sdk::hdk_extensions;
 
-use std::collections::HashMap;
+use std::collections::{
+    BTreeSet,
+    HashMap,
+    HashSet,
+};
 use lazy_static::lazy_static;
 use hdk::prelude::*;
 use hdk_extensions::{
@@ -13,7 +32,6 @@ use hdk_extensions::{
     must_get,
     exists,
     resolve_action_addr,
-    follow_evolutions,
     follow_evolutions_using_authorities_with_exceptions,
     // Input Structs
     UpdateEntryInput,
@@ -33,10 +51,17 @@ use coop_content::{
     GroupEntry,
     ContributionsAnchorEntry,
     ArchivedContributionsAnchorEntry,
+    ContributionAnchors,
     ContributionAnchorTypes,
+    ContributorsDiff,
+    MembershipWindow,
 };
 use coop_content_sdk::{
     create_link_input,
+    with_governing_revision,
+
+    // Link Tags
+    ContributionUpdateTag,
 
     // Input Structs
     GroupAuthInput,
@@ -44,8 +69,41 @@ use coop_content_sdk::{
     GetGroupContentInput,
     CreateContributionLinkInput,
     CreateContributionUpdateLinkInput,
+
+    // Output Structs
+    GroupGraph,
+    GroupGraphRevision,
+    GroupGraphAnchor,
+    GroupGraphLink,
+    GroupGraphLinkType,
+};
+use scoped_types::{
+    ContributionFilter,
+    entry_traits::*,
+};
+use group_forks::{
+    latest_group_revision,
+    canonical_group_revisions,
 };
-use scoped_types::entry_traits::*;
+use content_ordering::{
+    resolve_content_positions,
+    sort_by_position,
+};
+use group_resignations::{
+    group_resignations,
+};
+use key_succession::{
+    key_successors,
+    merged_key_anchors,
+    with_key_successors,
+};
+use contribution_windows::{
+    contribution_windows,
+    unbounded_contributors,
+    bounded_update_targets,
+};
+use group_keys::update_group_key;
+use group_capabilities::check_group_capability;
 
 
 lazy_static! {
@@ -56,7 +114,6 @@ lazy_static! {
 }
 
 type LinkPointerMap = HashMap<AnyLinkableHash, AnyLinkableHash>;
-type EvolutionMap = HashMap<AnyLinkableHash, Vec<AnyLinkableHash>>;
 
 
 fn create_if_not_exists<'a, T, E, E2>(entry: &'a T) -> ExternResult<Option<ActionHash>>
@@ -77,13 +134,23 @@ where
 #[hdk_extern]
 fn init(_: ()) -> ExternResult<InitCallbackResult> {
     debug!("'{}' init", *ZOME_NAME );
+
+    // Allow group capability claims to be delivered by their grantors
+    create_cap_grant( CapGrantEntry {
+        tag: "receive_group_capability".to_string(),
+        access: CapAccess::Unrestricted,
+        functions: GrantedFunctions::Listed( BTreeSet::from([
+            ( zome_info()?.name, "receive_group_capability".into() ),
+        ]) ),
+    })?;
+
     Ok(InitCallbackResult::Pass)
 }
 
 
 #[hdk_extern]
 fn whoami(_: ()) -> ExternResult<AgentInfo> {
-    Ok( agent_info()? )
+    agent_info()
 }
 
 
@@ -107,59 +174,100 @@ pub fn create_group(group: GroupEntry) -> ExternResult<ActionHash> {
 }
 
 
-#[hdk_extern]
-pub fn update_group(input: UpdateEntryInput<GroupEntry>) -> ExternResult<ActionHash> {
-    debug!("Update group action: {}", input.base );
-    let group_id = trace_origin_root( &input.base )?.0;
-    let prev_group : GroupEntry = must_get( &input.base )?.try_into()?;
-    let contributors_diff = prev_group.contributors_diff( &input.entry );
+/// Copy the links of an agent's contributions anchor to their archive anchor for a group revision
+///
+/// A time-bounded member's links are only copied from within their contribution window.
+fn archive_contributions_anchor(
+    group_id: &ActionHash,
+    group_rev: &ActionHash,
+    pubkey: &AgentPubKey,
+    window: Option<&MembershipWindow>,
+) -> ExternResult<()> {
+    let anchor = ContributionsAnchorEntry( group_id.to_owned(), pubkey.to_owned() );
+    let anchor_hash = hash_entry( &anchor )?;
+    let archive_anchor = ArchivedContributionsAnchorEntry::new( group_rev.to_owned(), pubkey.to_owned() );
+    let archive_anchor_hash = hash_entry( &archive_anchor )?;
 
-    let action_hash = update_entry( input.base.to_owned(), input.entry.to_input() )?;
+    create_if_not_exists( &archive_anchor )?;
+    create_link( group_rev.to_owned(), archive_anchor_hash.to_owned(), LinkTypes::GroupAuthArchive, () )?;
 
-    let archive_links = get_links(
+    let creates = get_links(
         create_link_input(
-            &input.base,
-            &LinkTypes::GroupAuthArchive,
+            &anchor_hash,
+            &LinkTypes::Contribution,
             &None::<()>,
         )?
     )?;
+    let updates = get_links(
+        create_link_input(
+            &anchor_hash,
+            &LinkTypes::ContributionUpdate,
+            &None::<()>,
+        )?
+    )?;
+    let tombstones = anchor.tombstone_links()?;
+    let positions = anchor.position_links()?;
+
+    let is_counted = |link: &Link| match window {
+        Some(window) => window.contains( &link.timestamp ),
+        None => true,
+    };
+    let creates : Vec<Link> = creates.into_iter().filter( is_counted ).collect();
+    let updates : Vec<Link> = updates.into_iter().filter( is_counted ).collect();
+    let tombstones : Vec<Link> = tombstones.into_iter().filter( is_counted ).collect();
+    let positions : Vec<Link> = positions.into_iter().filter( is_counted ).collect();
+
+    debug!("Copying {} creates for auth archive: {}", creates.len(), pubkey );
+    for link in creates {
+        create_link( archive_anchor_hash.to_owned(), link.target, LinkTypes::Contribution, link.tag )?;
+    }
+
+    debug!("Copying {} updates for auth archive: {}", updates.len(), pubkey );
+    for link in updates {
+        create_link( archive_anchor_hash.to_owned(), link.target, LinkTypes::ContributionUpdate, link.tag )?;
+    }
+
+    debug!("Copying {} tombstones for auth archive: {}", tombstones.len(), pubkey );
+    for link in tombstones {
+        let tag = content_tombstones::archived_tombstone_tag( &link )?;
+        create_link( archive_anchor_hash.to_owned(), link.target, LinkTypes::ContentTombstone, tag )?;
+    }
+
+    debug!("Copying {} positions for auth archive: {}", positions.len(), pubkey );
+    for link in positions {
+        let tag = content_ordering::archived_position_tag( &link )?;
+        create_link( archive_anchor_hash.to_owned(), link.target, LinkTypes::ContentPosition, tag )?;
+    }
+
+    Ok(())
+}
+
+/// Create the links for a new group revision based on the contributor changes from its base
+///
+/// The new revision is registered in the group's revision index and the given archive links (from
+/// the base revision) are carried forward to it.  A removed contributor's key successors are
+/// archived along with them, bounded by the contributor's window (from the base revision).
+pub(crate) fn create_group_revision_links(
+    group_id: &ActionHash,
+    group_rev: &ActionHash,
+    contributors_diff: ContributorsDiff,
+    archive_links: Vec<Link>,
+    windows: &HashMap<AgentPubKey, MembershipWindow>,
+) -> ExternResult<()> {
+    create_link( group_id.to_owned(), group_rev.to_owned(), LinkTypes::GroupRevision, () )?;
+
     for link in archive_links {
-        create_link( action_hash.to_owned(), link.target, LinkTypes::GroupAuthArchive, link.tag )?;
+        create_link( group_rev.to_owned(), link.target, LinkTypes::GroupAuthArchive, link.tag )?;
     }
 
     for pubkey in contributors_diff.removed {
         debug!("Removed Agent: {}", pubkey );
-        let anchor = ContributionsAnchorEntry( group_id.to_owned(), pubkey.to_owned() );
-        let anchor_hash = hash_entry( &anchor )?;
-        let archive_anchor = ArchivedContributionsAnchorEntry::new( action_hash.to_owned(), pubkey.to_owned() );
-        let archive_anchor_hash = hash_entry( &archive_anchor )?;
-
-        create_if_not_exists( &archive_anchor )?;
-        create_link( action_hash.to_owned(), archive_anchor_hash.to_owned(), LinkTypes::GroupAuthArchive, () )?;
-
-        let creates = get_links(
-            create_link_input(
-                &anchor_hash,
-                &LinkTypes::Contribution,
-                &None::<()>,
-            )?
-        )?;
-        let updates = get_links(
-            create_link_input(
-                &anchor_hash,
-                &LinkTypes::ContributionUpdate,
-                &None::<()>,
-            )?
-        )?;
-
-        debug!("Copying {} creates for auth archive: {}", creates.len(), pubkey );
-        for link in creates {
-            create_link( archive_anchor_hash.to_owned(), link.target, LinkTypes::Contribution, link.tag )?;
-        }
+        let window = windows.get( &pubkey );
+        archive_contributions_anchor( group_id, group_rev, &pubkey, window )?;
 
-        debug!("Copying {} updates for auth archive: {}", updates.len(), pubkey );
-        for link in updates {
-            create_link( archive_anchor_hash.to_owned(), link.target, LinkTypes::ContributionUpdate, link.tag )?;
+        for successor in key_successors( group_id, &pubkey )? {
+            debug!("Removed Agent successor: {}", successor );
+            archive_contributions_anchor( group_id, group_rev, &successor, window )?;
         }
     }
 
@@ -168,16 +276,44 @@ pub fn update_group(input: UpdateEntryInput<GroupEntry>) -> ExternResult<ActionH
         let anchor = ContributionsAnchorEntry( group_id.to_owned(), pubkey.to_owned() );
         let anchor_hash = hash_entry( &anchor )?;
         create_if_not_exists( &anchor )?;
-        create_link( action_hash.to_owned(), anchor_hash, LinkTypes::GroupAuth, () )?;
+        create_link( group_rev.to_owned(), anchor_hash, LinkTypes::GroupAuth, () )?;
     }
 
     for pubkey in contributors_diff.intersection {
         debug!("Unchanged Agent: {}", pubkey );
         let anchor = ContributionsAnchorEntry( group_id.to_owned(), pubkey.to_owned() );
         let anchor_hash = hash_entry( &anchor )?;
-        create_link( action_hash.to_owned(), anchor_hash, LinkTypes::GroupAuth, () )?;
+        create_link( group_rev.to_owned(), anchor_hash, LinkTypes::GroupAuth, () )?;
     }
 
+    Ok(())
+}
+
+
+#[hdk_extern]
+pub fn update_group(input: UpdateEntryInput<GroupEntry>) -> ExternResult<ActionHash> {
+    debug!("Update group action: {}", input.base );
+    let group_id = trace_origin_root( &input.base )?.0;
+    let prev_group : GroupEntry = must_get( &input.base )?.try_into()?;
+    let group = input.entry;
+
+    // Resignations must be formalised by the update rather than carried forward
+    let resignations = group_resignations( &input.base )?;
+
+    if let Some(member) = group.members.iter().find(|member| resignations.contains_key( member ) ) {
+        Err(guest_error!(format!("Member ({}) has resigned from the group and must be removed in the update", member )))?
+    }
+
+    let contributors_diff = prev_group.contributors_diff( &group );
+
+    let action_hash = update_entry( input.base.to_owned(), group.to_input() )?;
+    let archive_links = GroupEntry::group_auth_archive_links( &input.base )?;
+
+    let windows = contribution_windows( &prev_group, &resignations );
+
+    create_group_revision_links( &group_id, &action_hash, contributors_diff, archive_links, &windows )?;
+    update_group_key( &group_id, &action_hash )?;
+
     Ok( action_hash )
 }
 
@@ -185,29 +321,145 @@ pub fn update_group(input: UpdateEntryInput<GroupEntry>) -> ExternResult<ActionH
 #[hdk_extern]
 pub fn get_group(group_id: ActionHash) -> ExternResult<GroupEntry> {
     debug!("Get latest group entry: {}", group_id );
-    let latest_addr = follow_evolutions( &group_id )?.last().unwrap().to_owned();
+    let latest_addr = latest_group_revision( &group_id )?;
     let record = must_get( &latest_addr )?;
 
-    Ok( GroupEntry::try_from_record( &record )? )
+    GroupEntry::try_from_record( &record )
+}
+
+
+fn graph_link(
+    link_type: GroupGraphLinkType,
+    base: impl Into<AnyLinkableHash>,
+    link: Link
+) -> GroupGraphLink {
+    GroupGraphLink {
+        link_type,
+        create_link_hash: link.create_link_hash,
+        base: base.into(),
+        target: link.target,
+        tag: link.tag,
+        author: link.author,
+        timestamp: link.timestamp,
+    }
+}
+
+#[hdk_extern]
+pub fn export_group_graph(group_id: ActionHash) -> ExternResult<GroupGraph> {
+    debug!("Export group graph: {}", group_id );
+    let mut revisions = vec![];
+    let mut anchor_addrs : Vec<EntryHash> = vec![];
+    let mut links = vec![];
+
+    for group_rev in canonical_group_revisions( &group_id )? {
+        let record = must_get( &group_rev )?;
+        let previous = match record.action() {
+            Action::Update(update) => Some( update.original_action_address.to_owned() ),
+            _ => None,
+        };
+
+        revisions.push( GroupGraphRevision {
+            action: group_rev.to_owned(),
+            previous,
+            author: record.action().author().to_owned(),
+            timestamp: record.action().timestamp(),
+            group: GroupEntry::try_from_record( &record )?,
+        });
+
+        let auth_links = GroupEntry::group_auth_links( &group_rev )?.into_iter()
+            .map(|link| (GroupGraphLinkType::GroupAuth, link) );
+        let archive_links = GroupEntry::group_auth_archive_links( &group_rev )?.into_iter()
+            .map(|link| (GroupGraphLinkType::GroupAuthArchive, link) );
+
+        for (link_type, link) in auth_links.chain( archive_links ) {
+            if let Some(anchor_addr) = link.target.clone().into_entry_hash() {
+                if !anchor_addrs.contains( &anchor_addr ) {
+                    anchor_addrs.push( anchor_addr );
+                }
+            }
+            links.push( graph_link( link_type, group_rev.to_owned(), link ) );
+        }
+    }
+
+    let mut anchors = vec![];
+
+    debug!("Found {} contributions anchors for group '{}'", anchor_addrs.len(), group_id );
+    for anchor_addr in anchor_addrs {
+        let anchor : ContributionAnchors = must_get( &anchor_addr )?.try_into()?;
+        let (create_links, update_links, tombstone_links, position_links) = match &anchor {
+            ContributionAnchors::Active(anchor) => ( anchor.create_links()?, anchor.update_links()?, anchor.tombstone_links()?, anchor.position_links()? ),
+            ContributionAnchors::Archive(anchor) => ( anchor.create_links()?, anchor.update_links()?, anchor.tombstone_links()?, anchor.position_links()? ),
+        };
+
+        for link in create_links {
+            links.push( graph_link( GroupGraphLinkType::Contribution, anchor_addr.to_owned(), link ) );
+        }
+
+        for link in update_links {
+            links.push( graph_link( GroupGraphLinkType::ContributionUpdate, anchor_addr.to_owned(), link ) );
+        }
+
+        for link in tombstone_links {
+            links.push( graph_link( GroupGraphLinkType::ContentTombstone, anchor_addr.to_owned(), link ) );
+        }
+
+        for link in position_links {
+            links.push( graph_link( GroupGraphLinkType::ContentPosition, anchor_addr.to_owned(), link ) );
+        }
+
+        anchors.push( GroupGraphAnchor {
+            address: anchor_addr,
+            anchor,
+        });
+    }
+
+    Ok( GroupGraph {
+        group_id,
+        revisions,
+        anchors,
+        links,
+    })
 }
 
 
 #[hdk_extern]
 pub fn get_all_group_content_targets(input: GetAllGroupContentInput) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash)>> {
-    match input.full_trace {
-        None | Some(false) => get_all_group_content_targets_shortcuts( input.group_id ),
-        Some(true) => get_all_group_content_targets_full_trace( input.group_id ),
+    let filter = ContributionFilter {
+        collection: input.collection,
+        kind: input.kind,
+        window: None,
+    };
+
+    let mut targets = match input.full_trace {
+        None | Some(false) => group_content_targets_shortcuts( &input.group_id, &filter )?,
+        Some(true) => group_content_targets_full_trace( &input.group_id, &filter )?,
+    };
+
+    if input.sorted == Some(true) {
+        let collection = filter.collection.as_deref().unwrap_or("");
+        let positions = resolve_content_positions( &input.group_id, collection )?;
+        sort_by_position( &mut targets, &positions );
     }
+
+    Ok( targets )
 }
 
 
 #[hdk_extern]
 pub fn get_all_group_content_targets_full_trace(group_id: ActionHash) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash)>> {
-    debug!("Get latest group content: {}", group_id );
-    let latest_addr = follow_evolutions( &group_id )?.last().unwrap().to_owned();
+    group_content_targets_full_trace( &group_id, &ContributionFilter::default() )
+}
+
+fn group_content_targets_full_trace(
+    group_id: &ActionHash,
+    filter: &ContributionFilter,
+) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash)>> {
+    debug!("Get latest group content: {} ({:?})", group_id, filter );
+    let latest_addr = latest_group_revision( group_id )?;
     let record = must_get( &latest_addr )?;
     let group_rev = record.action_address().to_owned();
     let group : GroupEntry = record.try_into()?;
+    let windows = contribution_windows( &group, &group_resignations( &group_rev )? );
 
     let mut content_creates = vec![];
     let mut archived_updates : Vec<ActionHash> = vec![];
@@ -217,7 +469,7 @@ pub fn get_all_group_content_targets_full_trace(group_id: ActionHash) -> ExternR
     debug!("Found {} auth archives for group rev '{}'", auth_archive_anchors.len(), group_rev );
     for auth_archive_addr in auth_archive_anchors.iter() {
         let anchor : ArchivedContributionsAnchorEntry = must_get( auth_archive_addr )?.try_into()?;
-        content_creates.extend( anchor.create_targets()? );
+        content_creates.extend( anchor.create_filtered_targets( filter )? );
 
         let archive_updates = anchor.update_targets()?;
         let update_actions : Vec<ActionHash> = archive_updates.iter()
@@ -233,17 +485,31 @@ pub fn get_all_group_content_targets_full_trace(group_id: ActionHash) -> ExternR
     debug!("Found {} current contributors for group rev '{}'", group_auth_anchors.len(), group_rev );
     for auth_anchor_addr in group_auth_anchors.iter() {
         let anchor : ContributionsAnchorEntry = must_get( auth_anchor_addr )?.try_into()?;
-        let content_targets = anchor.create_targets()?;
-        debug!("Found {} content links for group contributor '{}'", content_targets.len(), anchor.1 );
-        content_creates.extend( content_targets );
+        let window = windows.get( anchor.author() ).cloned();
+
+        // The anchors of a contributor's key successors are merged with their own
+        for anchor in merged_key_anchors( anchor )? {
+            let content_targets = anchor.create_filtered_targets( &ContributionFilter {
+                window: window.to_owned(),
+                ..filter.to_owned()
+            })?;
+            debug!("Found {} content links for group contributor '{}'", content_targets.len(), anchor.1 );
+            content_creates.extend( content_targets );
+
+            // A time-bounded member's contributions are treated like an archive
+            if let Some(window) = &window {
+                archived_updates.extend( bounded_update_targets( &anchor, window )? );
+            }
+        }
     }
 
+    let authorities = with_key_successors( group_id, unbounded_contributors( &group, &windows ) )?;
     let mut targets = vec![];
 
-    for content_addr in content_creates {
+    for content_addr in unique_content_ids( content_creates ) {
         match content_addr.clone().into_action_hash() {
             Some(addr) => {
-                let evolutions = follow_evolutions_using_authorities_with_exceptions( &addr, &group.contributors(), &archived_updates )?;
+                let evolutions = follow_evolutions_using_authorities_with_exceptions( &addr, &authorities, &archived_updates )?;
                 targets.push((
                     content_addr,
                     evolutions.last().unwrap().to_owned().into()
@@ -257,6 +523,18 @@ pub fn get_all_group_content_targets_full_trace(group_id: ActionHash) -> ExternR
 }
 
 
+/// Remove repeated content IDs (keeping the first occurrence)
+///
+/// The same content ID can be registered by more than one anchor, such as the archive copy and the
+/// original active anchor of a member that was removed and then re-added.
+fn unique_content_ids(content_ids: Vec<AnyLinkableHash>) -> Vec<AnyLinkableHash> {
+    let mut seen = HashSet::new();
+
+    content_ids.into_iter()
+        .filter(|content_id| seen.insert( content_id.to_owned() ) )
+        .collect()
+}
+
 fn follow_update_map(
     start: &AnyLinkableHash,
     updates: &LinkPointerMap
@@ -275,10 +553,19 @@ fn follow_update_map(
 
 #[hdk_extern]
 pub fn follow_all_group_content_evolutions_shortcuts(group_id: ActionHash) -> ExternResult<Vec<(AnyLinkableHash, Vec<AnyLinkableHash>)>> {
-    debug!("Get latest group content: {}", group_id );
-    let latest_addr = follow_evolutions( &group_id )?.last().unwrap().to_owned();
+    follow_group_content_evolutions_shortcuts( &group_id, &ContributionFilter::default() )
+}
+
+fn follow_group_content_evolutions_shortcuts(
+    group_id: &ActionHash,
+    filter: &ContributionFilter,
+) -> ExternResult<Vec<(AnyLinkableHash, Vec<AnyLinkableHash>)>> {
+    debug!("Get latest group content: {} ({:?})", group_id, filter );
+    let latest_addr = latest_group_revision( group_id )?;
     let record = must_get( &latest_addr )?;
     let group_rev = record.action_address().to_owned();
+    let group : GroupEntry = record.try_into()?;
+    let windows = contribution_windows( &group, &group_resignations( &group_rev )? );
 
     let mut targets = vec![];
     let mut updates = HashMap::new();
@@ -290,7 +577,7 @@ pub fn follow_all_group_content_evolutions_shortcuts(group_id: ActionHash) -> Ex
         let anchor : ArchivedContributionsAnchorEntry = must_get( auth_archive_addr )?.try_into()?;
         debug!("Auth archive anchor: {:#?}", anchor );
 
-        let content_ids = anchor.create_targets()?;
+        let content_ids = anchor.create_filtered_targets( filter )?;
         debug!("Found {} content IDs: {:#?}", content_ids.len(), content_ids );
         targets.extend( content_ids );
 
@@ -308,20 +595,29 @@ pub fn follow_all_group_content_evolutions_shortcuts(group_id: ActionHash) -> Ex
         let anchor : ContributionsAnchorEntry = must_get( auth_anchor_addr )?.try_into()?;
         debug!("Auth anchor: {:#?}", anchor );
 
-        let content_ids = anchor.create_targets()?;
-        debug!("Found {} content IDs: {:#?}", content_ids.len(), content_ids );
-        targets.extend( content_ids );
-
-        let shortcuts = anchor.shortcuts()?;
-        debug!("Found {} content update shortcuts: {:#?}", shortcuts.len(), shortcuts );
-        for (_,base,target) in shortcuts {
-            updates.insert( base, target );
+        // A time-bounded member's links are only counted within their window
+        let window = windows.get( anchor.author() ).cloned();
+
+        // The anchors of a contributor's key successors are merged with their own
+        for anchor in merged_key_anchors( anchor )? {
+            let content_ids = anchor.create_filtered_targets( &ContributionFilter {
+                window: window.to_owned(),
+                ..filter.to_owned()
+            })?;
+            debug!("Found {} content IDs: {:#?}", content_ids.len(), content_ids );
+            targets.extend( content_ids );
+
+            let shortcuts = anchor.shortcuts_within( window.as_ref() )?;
+            debug!("Found {} content update shortcuts: {:#?}", shortcuts.len(), shortcuts );
+            for (_,base,target) in shortcuts {
+                updates.insert( base, target );
+            }
         }
     }
 
     let mut content_evolutions = vec![];
 
-    for addr in targets {
+    for addr in unique_content_ids( targets ) {
         content_evolutions.push((
             addr.clone(),
             follow_update_map( &addr, &updates )
@@ -333,8 +629,15 @@ pub fn follow_all_group_content_evolutions_shortcuts(group_id: ActionHash) -> Ex
 
 #[hdk_extern]
 pub fn get_all_group_content_targets_shortcuts(group_id: ActionHash) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash)>> {
+    group_content_targets_shortcuts( &group_id, &ContributionFilter::default() )
+}
+
+fn group_content_targets_shortcuts(
+    group_id: &ActionHash,
+    filter: &ContributionFilter,
+) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash)>> {
     Ok(
-        follow_all_group_content_evolutions_shortcuts( group_id )?.into_iter()
+        follow_group_content_evolutions_shortcuts( group_id, filter )?.into_iter()
             .filter_map( |(key, evolutions)| {
                 let latest_addr = evolutions.last()?.to_owned();
                 Some( (key, latest_addr) )
@@ -346,40 +649,62 @@ pub fn get_all_group_content_targets_shortcuts(group_id: ActionHash) -> ExternRe
 
 #[hdk_extern]
 pub fn group_auth_anchor_hash(input: GroupAuthInput) -> ExternResult<EntryHash> {
-    Ok( hash_entry( ContributionsAnchorEntry( input.group_id, input.author ) )? )
+    hash_entry( ContributionsAnchorEntry( input.group_id, input.author ) )
 }
 
 #[hdk_extern]
 pub fn group_auth_archive_anchor_hash(input: GroupAuthInput) -> ExternResult<EntryHash> {
-    Ok( hash_entry( ArchivedContributionsAnchorEntry::new( input.group_id, input.author ) )? )
+    hash_entry( ArchivedContributionsAnchorEntry::new( input.group_id, input.author ) )
 }
 
 
 #[hdk_extern]
 pub fn create_content_link(input: CreateContributionLinkInput) -> ExternResult<ActionHash> {
+    check_group_capability( &input.group_id )?;
     let author = agent_id()?;
     debug!("Creating content link from ContributionsAnchorEntry( {}, {} ) => {}", input.group_id, author, input.content_target );
+    let group_rev = latest_group_revision( &input.group_id )?;
     let anchor = ContributionsAnchorEntry( input.group_id, author );
     let anchor_hash = hash_entry( &anchor )?;
+    let payload = match input.tag {
+        Some(tag) => LinkTag::try_from( tag )?.into_inner(),
+        None => vec![],
+    };
+    let tag = with_governing_revision( payload, &group_rev );
 
     create_if_not_exists( &anchor )?;
 
-    Ok( create_link( anchor_hash, input.content_target, LinkTypes::Contribution, () )? )
+    let contribution_addr = create_link( anchor_hash.to_owned(), input.content_target.to_owned(), LinkTypes::Contribution, tag )?;
+
+    // Reverse index so that the groups of a content address can be found
+    create_link(
+        input.content_target,
+        anchor_hash,
+        LinkTypes::ContributionIndex,
+        contribution_addr.get_raw_39().to_vec(),
+    )?;
+
+    Ok( contribution_addr )
 }
 
 
 #[hdk_extern]
 pub fn create_content_update_link(input: CreateContributionUpdateLinkInput) -> ExternResult<ActionHash> {
+    check_group_capability( &input.group_id )?;
     let author = agent_id()?;
-    let tag = format!("{}:{}", input.content_id, input.content_prev );
+    let tag = ContributionUpdateTag {
+        content_id: input.content_id,
+        content_rev: input.content_prev,
+    };
+    let group_rev = latest_group_revision( &input.group_id )?;
     let anchor = ContributionsAnchorEntry( input.group_id, author );
     let anchor_hash = hash_entry( &anchor )?;
     debug!("Auth anchor: {:#?}", anchor );
 
     create_if_not_exists( &anchor )?;
 
-    debug!("Creating content update link from {} --'{}'--> {}", anchor_hash, tag, input.content_next );
-    Ok( create_link( anchor_hash, input.content_next, LinkTypes::ContributionUpdate, tag.into_bytes() )? )
+    debug!("Creating content update link from {} --'{:?}'--> {}", anchor_hash, tag, input.content_next );
+    create_link( anchor_hash, input.content_next, LinkTypes::ContributionUpdate, with_governing_revision( tag.to_bytes(), &group_rev ) )
 }
 
 
@@ -419,6 +744,24 @@ pub fn delete_group_auth_anchor_content_links(input: (GroupAuthInput, AnyLinkabl
         }
     }
 
+    // Remove the reverse index links of the deleted contributions (only the index author can)
+    let author = agent_id()?;
+    let index_links = get_links(
+        create_link_input(
+            &input.1,
+            &LinkTypes::ContributionIndex,
+            &None::<()>,
+        )?
+    )?;
+
+    for link in index_links {
+        if link.author == author
+            && deleted.iter().any(|addr| addr.get_raw_39() == link.tag.0.as_slice() )
+        {
+            delete_link( link.create_link_hash )?;
+        }
+    }
+
     Ok( deleted )
 }
 
@@ -436,10 +779,11 @@ pub fn get_group_content_evolutions(input: GetGroupContentInput) -> ExternResult
 pub fn get_group_content_evolutions_full_trace(input: GetGroupContentInput) -> ExternResult<Vec<AnyLinkableHash>> {
     debug!("Get group ({}) content evolutions (full-trace): {}", input.group_id, input.content_id );
     let base_addr = resolve_action_addr( &input.content_id )?;
-    let latest_addr = follow_evolutions( &input.group_id )?.last().unwrap().to_owned();
+    let latest_addr = latest_group_revision( &input.group_id )?;
     let record = must_get( &latest_addr )?;
     let group_rev = record.action_address().to_owned();
     let group : GroupEntry = record.try_into()?;
+    let windows = contribution_windows( &group, &group_resignations( &group_rev )? );
 
     let mut archived_updates : Vec<ActionHash> = vec![];
     let auth_archive_anchors = GroupEntry::group_auth_archive_anchor_hashes( &group_rev )?;
@@ -457,27 +801,115 @@ pub fn get_group_content_evolutions_full_trace(input: GetGroupContentInput) -> E
         archived_updates.extend( update_actions );
     }
 
+    for (agent, window) in windows.iter() {
+        let anchor = ContributionsAnchorEntry( input.group_id.to_owned(), agent.to_owned() );
+
+        for anchor in merged_key_anchors( anchor )? {
+            archived_updates.extend( bounded_update_targets( &anchor, window )? );
+        }
+    }
+
     Ok(
         follow_evolutions_using_authorities_with_exceptions(
             &base_addr,
-            &group.contributors(),
+            &with_key_successors( &input.group_id, unbounded_contributors( &group, &windows ) )?,
             &archived_updates
         )?.into_iter().map( |hash| hash.into() ).collect()
     )
 }
 
+/// Check if a content ID is registered by a contribution link in a group revision
+///
+/// Only the anchors of the agents that could have registered the content are checked (the agents
+/// with a contribution index link to it, and the content's author for contributions made before
+/// the index existed) so that the cost does not depend on the size of the group.
+pub(crate) fn is_group_content(group_rev: &ActionHash, content_id: &AnyLinkableHash) -> ExternResult<bool> {
+    let index_links = get_links(
+        create_link_input(
+            content_id,
+            &LinkTypes::ContributionIndex,
+            &None::<()>,
+        )?
+    )?;
+    let index_anchors : Vec<AnyLinkableHash> = index_links.iter()
+        .map(|link| link.target.to_owned() )
+        .collect();
+    let mut agents : Vec<AgentPubKey> = index_links.into_iter()
+        .map(|link| link.author )
+        .collect();
+
+    if let Some(content_addr) = content_id.clone().into_action_hash() {
+        if let Some(record) = get( content_addr, GetOptions::default() )? {
+            agents.push( record.action().author().to_owned() );
+        }
+    }
+
+    for auth_anchor_addr in GroupEntry::group_auth_anchor_hashes( group_rev )? {
+        let anchor : ContributionsAnchorEntry = must_get( &auth_anchor_addr )?.try_into()?;
+
+        for anchor in merged_key_anchors( anchor )? {
+            if index_anchors.contains( &anchor.base_hash()?.into() ) {
+                return Ok( true );
+            }
+
+            if agents.contains( anchor.author() ) && anchor.create_targets()?.contains( content_id ) {
+                return Ok( true );
+            }
+        }
+    }
+
+    for auth_archive_addr in GroupEntry::group_auth_archive_anchor_hashes( group_rev )? {
+        let anchor : ArchivedContributionsAnchorEntry = must_get( &auth_archive_addr )?.try_into()?;
+
+        if agents.contains( anchor.author() ) && anchor.create_targets()?.contains( content_id ) {
+            return Ok( true );
+        }
+    }
+
+    Ok( false )
+}
+
+/// Follow the shortcuts of a single content ID
+///
+/// Only the update links tagged with the content ID are loaded (using a `tag_prefix`) so the cost
+/// depends on the content's own history rather than the size of the group.  A content ID without
+/// any update links resolves to itself.
 #[hdk_extern]
 pub fn get_group_content_evolutions_shortcuts(input: GetGroupContentInput) -> ExternResult<Vec<AnyLinkableHash>> {
     debug!("Get group ({}) content evolutions (shortcuts): {}", input.group_id, input.content_id );
-    let all_content_evolutions : EvolutionMap = follow_all_group_content_evolutions_shortcuts( input.group_id )?
-        .into_iter().collect();
+    let latest_addr = latest_group_revision( &input.group_id )?;
+    let record = must_get( &latest_addr )?;
+    let group_rev = record.action_address().to_owned();
+    let group : GroupEntry = record.try_into()?;
 
-    debug!("Looking for {} in: {:#?}", input.content_id, all_content_evolutions );
-    let evolutions = all_content_evolutions.get( &input.content_id.clone().into() )
-        .ok_or(guest_error!(format!("Content ID ({}) is not in group content: {:?}", input.content_id, all_content_evolutions.keys() )))?
-        .to_owned();
+    if !is_group_content( &group_rev, &input.content_id )? {
+        Err(guest_error!(format!("Content ID ({}) is not in group content", input.content_id )))?
+    }
+
+    let windows = contribution_windows( &group, &group_resignations( &group_rev )? );
+    let mut updates = HashMap::new();
+
+    for auth_archive_addr in GroupEntry::group_auth_archive_anchor_hashes( &group_rev )? {
+        let anchor : ArchivedContributionsAnchorEntry = must_get( &auth_archive_addr )?.try_into()?;
+
+        for (_,base,target) in anchor.content_shortcuts( &input.content_id )? {
+            updates.insert( base, target );
+        }
+    }
+
+    for auth_anchor_addr in GroupEntry::group_auth_anchor_hashes( &group_rev )? {
+        let anchor : ContributionsAnchorEntry = must_get( &auth_anchor_addr )?.try_into()?;
+        let window = windows.get( anchor.author() ).cloned();
+
+        for anchor in merged_key_anchors( anchor )? {
+            for (_,base,target) in anchor.content_shortcuts_within( &input.content_id, window.as_ref() )? {
+                updates.insert( base, target );
+            }
+        }
+    }
 
-    Ok( evolutions )
+    debug!("Found {} content update shortcuts for {}", updates.len(), input.content_id );
+    Ok( follow_update_map( &input.content_id, &updates ) )
 }
 
 
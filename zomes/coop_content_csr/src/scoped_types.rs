@@ -1,9 +1,6 @@
 pub mod entry_traits;
 
 use crate::hdk::prelude::*;
-use crate::hdi_extensions::{
-    AnyLinkableHashTransformer,
-};
 use coop_content::{
     LinkTypes,
 };
@@ -14,6 +11,10 @@ use coop_content_sdk::{
     GroupEntry,
     ContributionsAnchorEntry,
     ArchivedContributionsAnchorEntry,
+    ContributionTag,
+    ContributionUpdateTag,
+    MembershipWindow,
+    governed_payload,
 };
 pub use entry_traits::{
     GroupLinks,
@@ -23,15 +24,134 @@ pub use entry_traits::{
 
 
 
+/// Get an anchor's update links for a single content ID
+///
+/// Uses a `tag_prefix` for each supported tag format so that only the links of the given content
+/// are loaded.
+fn content_update_links(base: &EntryHash, content_id: &AnyLinkableHash) -> ExternResult<Vec<Link>> {
+    let prefixes = [
+        ContributionUpdateTag::content_id_prefix( content_id ),
+        ContributionUpdateTag::legacy_content_id_prefix( content_id ),
+    ];
+    let mut links = vec![];
+
+    for prefix in prefixes {
+        links.extend( get_links(
+            create_link_input(
+                base,
+                &LinkTypes::ContributionUpdate,
+                &Some( prefix ),
+            )?
+        )? );
+    }
+
+    Ok( links )
+}
+
+/// Keep the links made within the given window (all links when there is no window)
+fn links_within(links: Vec<Link>, window: Option<&MembershipWindow>) -> Vec<Link> {
+    links.into_iter()
+        .filter(|link| match window {
+            Some(window) => window.contains( &link.timestamp ),
+            None => true,
+        })
+        .collect()
+}
+
+/// Parse update links into (content ID, content revision, target) shortcuts
+///
+/// Links with a malformed tag are skipped.
+fn update_link_shortcuts(links: Vec<Link>) -> Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)> {
+    links.into_iter()
+        .filter_map(|link| {
+            match ContributionUpdateTag::from_bytes( governed_payload( &link.tag.0 ) ) {
+                Ok(tag) => Some((tag.content_id, tag.content_rev, link.target)),
+                Err(message) => {
+                    debug!("{}", message );
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+
+/// Narrows an anchor's contribution links to a collection and/or content kind
+///
+/// A kind without a collection selects from the default collection.
+#[derive(Clone, Debug, Default)]
+pub struct ContributionFilter {
+    pub collection: Option<String>,
+    pub kind: Option<String>,
+    /// Only include links created within this window (eg. a bounded or resigned member)
+    pub window: Option<MembershipWindow>,
+}
+
+fn filtered_contribution_links(
+    base: &EntryHash,
+    filter: &ContributionFilter,
+) -> ExternResult<Vec<Link>> {
+    let collection = filter.collection.as_deref().unwrap_or("");
+    let get_prefixed = |prefix: Option<Vec<u8>>| -> ExternResult<Vec<Link>> {
+        get_links(
+            create_link_input(
+                base,
+                &LinkTypes::Contribution,
+                &prefix,
+            )?
+        )
+    };
+
+    let links = match (&filter.collection, &filter.kind) {
+        (None, None) => get_prefixed( None ),
+        (_, Some(kind)) => get_prefixed( Some( ContributionTag::kind_prefix( collection, kind ) ) ),
+        (Some(_), None) if !collection.is_empty() => {
+            get_prefixed( Some( ContributionTag::collection_prefix( collection ) ) )
+        },
+        (Some(_), None) => {
+            // The default collection also holds untagged contributions
+            Ok(
+                get_prefixed( None )?.into_iter()
+                    .filter(|link| matches!( ContributionTag::collection_of( governed_payload( &link.tag.0 ) ).as_deref(), Ok("") ) )
+                    .collect()
+            )
+        },
+    }?;
+
+    Ok(
+        links.into_iter()
+            .filter(|link| match &filter.window {
+                Some(window) => window.contains( &link.timestamp ),
+                None => true,
+            })
+            .collect()
+    )
+}
+
+
 impl GroupLinks for GroupEntry {
-    fn group_auth_anchor_hashes(base: &ActionHash) -> ExternResult<Vec<EntryHash>> {
-        let links = get_links(
+    fn group_auth_links(base: &ActionHash) -> ExternResult<Vec<Link>> {
+        get_links(
             create_link_input(
                 base,
                 &LinkTypes::GroupAuth,
                 &None::<()>,
             )?
-        )?;
+        )
+    }
+
+    fn group_auth_archive_links(base: &ActionHash) -> ExternResult<Vec<Link>> {
+        get_links(
+            create_link_input(
+                base,
+                &LinkTypes::GroupAuthArchive,
+                &None::<()>,
+            )?
+        )
+    }
+
+    fn group_auth_anchor_hashes(base: &ActionHash) -> ExternResult<Vec<EntryHash>> {
+        let links = Self::group_auth_links( base )?;
 
         Ok(
             links.into_iter()
@@ -46,13 +166,7 @@ impl GroupLinks for GroupEntry {
     }
 
     fn group_auth_archive_anchor_hashes(base: &ActionHash) -> ExternResult<Vec<EntryHash>> {
-        let links = get_links(
-            create_link_input(
-                base,
-                &LinkTypes::GroupAuthArchive,
-                &None::<()>,
-            )?
-        )?;
+        let links = Self::group_auth_archive_links( base )?;
 
         Ok(
             links.into_iter()
@@ -72,16 +186,28 @@ impl ContributionsLinks for ContributionsAnchorEntry {
         hash_entry( self )
     }
 
+    fn create_links(&self) -> ExternResult<Vec<Link>> {
+        get_links(
+            create_link_input(
+                &self.base_hash()?,
+                &LinkTypes::Contribution,
+                &None::<()>,
+            )?
+        )
+    }
+
     fn create_targets(&self) -> ExternResult<Vec<AnyLinkableHash>> {
-        let base = hash_entry( self )?;
         Ok(
-            get_links(
-                create_link_input(
-                    &base,
-                    &LinkTypes::Contribution,
-                    &None::<()>,
-                )?
-            )?
+            self.create_links()?
+                .into_iter()
+                .map(|link| link.target )
+                .collect()
+        )
+    }
+
+    fn create_filtered_targets(&self, filter: &ContributionFilter) -> ExternResult<Vec<AnyLinkableHash>> {
+        Ok(
+            filtered_contribution_links( &self.base_hash()?, filter )?
                 .into_iter()
                 .map(|link| link.target )
                 .collect()
@@ -98,32 +224,38 @@ impl ContributionsLinks for ContributionsAnchorEntry {
         )
     }
 
-    fn update_targets(&self) -> ExternResult<Vec<AnyLinkableHash>> {
-        Ok(
-            self.update_links()?
-                .into_iter()
-                .map(|link| link.target )
-                .collect()
+    fn tombstone_links(&self) -> ExternResult<Vec<Link>> {
+        get_links(
+            create_link_input(
+                &self.base_hash()?,
+                &LinkTypes::ContentTombstone,
+                &None::<()>,
+            )?
         )
     }
 
-    fn shortcuts(&self) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>> {
-        Ok(self.update_links()?.into_iter()
-            .filter_map(|link| {
-                let tag_str = String::from_utf8( link.tag.into_inner() ).ok()?;
-                let (tag_id, tag_rev) = tag_str.split_once(":")
-                    .or_else(|| {
-                        debug!("Contribution update link has malformed tag: {}", tag_str );
-                        None
-                    })?;
+    fn position_links(&self) -> ExternResult<Vec<Link>> {
+        get_links(
+            create_link_input(
+                &self.base_hash()?,
+                &LinkTypes::ContentPosition,
+                &None::<()>,
+            )?
+        )
+    }
 
-                Some((
-                    AnyLinkableHash::try_from_string( tag_id ).ok()?,
-                    AnyLinkableHash::try_from_string( tag_rev ).ok()?,
-                    link.target
-                ))
-            })
-            .collect())
+    fn shortcuts_within(&self, window: Option<&MembershipWindow>) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>> {
+        Ok( update_link_shortcuts( links_within( self.update_links()?, window ) ) )
+    }
+
+    fn content_shortcuts_within(
+        &self,
+        content_id: &AnyLinkableHash,
+        window: Option<&MembershipWindow>,
+    ) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>> {
+        let links = content_update_links( &self.base_hash()?, content_id )?;
+
+        Ok( update_link_shortcuts( links_within( links, window ) ) )
     }
 }
 
@@ -132,16 +264,28 @@ impl ArchivedContributionsLinks for ArchivedContributionsAnchorEntry {
         hash_entry( self )
     }
 
+    fn create_links(&self) -> ExternResult<Vec<Link>> {
+        get_links(
+            create_link_input(
+                &self.base_hash()?,
+                &LinkTypes::Contribution,
+                &None::<()>,
+            )?
+        )
+    }
+
     fn create_targets(&self) -> ExternResult<Vec<AnyLinkableHash>> {
-        let base = self.base_hash()?;
         Ok(
-            get_links(
-                create_link_input(
-                    &base,
-                    &LinkTypes::Contribution,
-                    &None::<()>,
-                )?
-            )?
+            self.create_links()?
+                .into_iter()
+                .map(|link| link.target )
+                .collect()
+        )
+    }
+
+    fn create_filtered_targets(&self, filter: &ContributionFilter) -> ExternResult<Vec<AnyLinkableHash>> {
+        Ok(
+            filtered_contribution_links( &self.base_hash()?, filter )?
                 .into_iter()
                 .map(|link| link.target )
                 .collect()
@@ -167,22 +311,31 @@ impl ArchivedContributionsLinks for ArchivedContributionsAnchorEntry {
         )
     }
 
+    fn tombstone_links(&self) -> ExternResult<Vec<Link>> {
+        get_links(
+            create_link_input(
+                &self.base_hash()?,
+                &LinkTypes::ContentTombstone,
+                &None::<()>,
+            )?
+        )
+    }
+
+    fn position_links(&self) -> ExternResult<Vec<Link>> {
+        get_links(
+            create_link_input(
+                &self.base_hash()?,
+                &LinkTypes::ContentPosition,
+                &None::<()>,
+            )?
+        )
+    }
+
     fn shortcuts(&self) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>> {
-        Ok(self.update_links()?.into_iter()
-            .filter_map(|link| {
-                let tag_str = String::from_utf8( link.tag.into_inner() ).ok()?;
-                let (tag_id, tag_rev) = tag_str.split_once(":")
-                    .or_else(|| {
-                        debug!("Contribution update link has malformed tag: {}", tag_str );
-                        None
-                    })?;
+        Ok( update_link_shortcuts( self.update_links()? ) )
+    }
 
-                Some((
-                    AnyLinkableHash::try_from_string( tag_id ).ok()?,
-                    AnyLinkableHash::try_from_string( tag_rev ).ok()?,
-                    link.target
-                ))
-            })
-           .collect())
+    fn content_shortcuts(&self, content_id: &AnyLinkableHash) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>> {
+        Ok( update_link_shortcuts( content_update_links( &self.base_hash()?, content_id )? ) )
     }
 }
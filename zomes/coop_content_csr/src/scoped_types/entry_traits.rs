@@ -1,24 +1,37 @@
 use crate::hdk::prelude::*;
+use coop_content_sdk::MembershipWindow;
+use super::ContributionFilter;
 
 pub trait GroupLinks {
+    fn group_auth_links(base: &ActionHash) -> ExternResult<Vec<Link>>;
+    fn group_auth_archive_links(base: &ActionHash) -> ExternResult<Vec<Link>>;
     fn group_auth_anchor_hashes(base: &ActionHash) -> ExternResult<Vec<EntryHash>>;
     fn group_auth_archive_anchor_hashes(base: &ActionHash) -> ExternResult<Vec<EntryHash>>;
 }
 
 pub trait ContributionsLinks {
     fn base_hash(&self) -> ExternResult<EntryHash>;
+    fn create_links(&self) -> ExternResult<Vec<Link>>;
     fn create_targets(&self) -> ExternResult<Vec<AnyLinkableHash>>;
+    fn create_filtered_targets(&self, filter: &ContributionFilter) -> ExternResult<Vec<AnyLinkableHash>>;
     fn update_links(&self) -> ExternResult<Vec<Link>>;
-    fn update_targets(&self) -> ExternResult<Vec<AnyLinkableHash>>;
-    fn shortcuts(&self) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>>;
+    fn tombstone_links(&self) -> ExternResult<Vec<Link>>;
+    fn position_links(&self) -> ExternResult<Vec<Link>>;
+    fn shortcuts_within(&self, window: Option<&MembershipWindow>) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>>;
+    fn content_shortcuts_within(&self, content_id: &AnyLinkableHash, window: Option<&MembershipWindow>) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>>;
 }
 
 
 pub trait ArchivedContributionsLinks {
     fn base_hash(&self) -> ExternResult<EntryHash>;
+    fn create_links(&self) -> ExternResult<Vec<Link>>;
     fn create_targets(&self) -> ExternResult<Vec<AnyLinkableHash>>;
+    fn create_filtered_targets(&self, filter: &ContributionFilter) -> ExternResult<Vec<AnyLinkableHash>>;
     fn update_links(&self) -> ExternResult<Vec<Link>>;
     fn update_targets(&self) -> ExternResult<Vec<AnyLinkableHash>>;
+    fn tombstone_links(&self) -> ExternResult<Vec<Link>>;
+    fn position_links(&self) -> ExternResult<Vec<Link>>;
     fn shortcuts(&self) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>>;
+    fn content_shortcuts(&self, content_id: &AnyLinkableHash) -> ExternResult<Vec<(AnyLinkableHash, AnyLinkableHash, AnyLinkableHash)>>;
 }
 